@@ -0,0 +1,416 @@
+use serde_json::Value;
+use std::{env, fmt::Write, fs, path::Path};
+
+fn main() {
+    let idl_path = Path::new(env!("CARGO_MANIFEST_DIR")).join("../../abi/gold_rush.json");
+    println!("cargo:rerun-if-changed={}", idl_path.display());
+
+    let raw = fs::read_to_string(&idl_path).expect("read abi/gold_rush.json");
+    let idl: Value = serde_json::from_str(&raw).expect("parse abi/gold_rush.json");
+
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR");
+    let out = Path::new(&out_dir);
+
+    fs::write(out.join("gold_rush_errors.rs"), gen_errors(&idl)).expect("write errors");
+    fs::write(out.join("gold_rush_idl.rs"), gen_idl(&idl)).expect("write idl");
+}
+
+/// `roundNotEnded` -> `RoundNotEnded`
+fn pascal_case(name: &str) -> String {
+    let mut chars = name.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// `finalizeStartGroups` -> `finalize_start_groups`
+fn snake_case(name: &str) -> String {
+    let mut s = String::new();
+    for (i, c) in name.chars().enumerate() {
+        if c.is_ascii_uppercase() {
+            if i > 0 {
+                s.push('_');
+            }
+            s.push(c.to_ascii_lowercase());
+        } else {
+            s.push(c);
+        }
+    }
+    s
+}
+
+/// Rust type of an IDL type
+fn rust_type(ty: &Value) -> String {
+    rust_type_with(ty, "Pubkey")
+}
+
+/// Rust type of an IDL type, naming pubkeys with the given path
+fn rust_type_with(ty: &Value, pubkey: &str) -> String {
+    if let Some(name) = ty.as_str() {
+        return match name {
+            "pubkey" => pubkey.to_string(),
+            "string" => "String".to_string(),
+            "bytes" => "Vec<u8>".to_string(),
+            // bool and the integer types share their Rust names
+            other => other.to_string(),
+        };
+    }
+    if let Some(inner) = ty.get("option") {
+        return format!("Option<{}>", rust_type_with(inner, pubkey));
+    }
+    if let Some(inner) = ty.get("vec") {
+        return format!("Vec<{}>", rust_type_with(inner, pubkey));
+    }
+    if let Some(array) = ty.get("array") {
+        return format!("[{}; {}]", rust_type_with(&array[0], pubkey), array[1]);
+    }
+    if let Some(name) = ty["defined"]["name"].as_str() {
+        return pascal_case(name);
+    }
+    panic!("unsupported IDL type: {}", ty);
+}
+
+/// `///` lines from an IDL `docs` array
+fn write_docs(s: &mut String, indent: &str, item: &Value) {
+    for line in item["docs"].as_array().into_iter().flatten() {
+        let line = line.as_str().unwrap_or("");
+        if line.is_empty() {
+            writeln!(s, "{}///", indent).unwrap();
+        } else {
+            writeln!(s, "{}/// {}", indent, line).unwrap();
+        }
+    }
+}
+
+/// `[1, 2, ...]` from an IDL discriminator
+fn discriminator(item: &Value) -> String {
+    let bytes: Vec<String> = item["discriminator"]
+        .as_array()
+        .expect("discriminator")
+        .iter()
+        .map(|b| b.as_u64().expect("discriminator byte").to_string())
+        .collect();
+    format!("[{}]", bytes.join(", "))
+}
+
+/// Emit the IDL types, account discriminators and instruction builders
+fn gen_idl(idl: &Value) -> String {
+    let mut s = String::new();
+    gen_types(&mut s, idl);
+    gen_instructions(&mut s, idl);
+    s
+}
+
+/// `types` module: every IDL type, with the discriminator of the account types
+fn gen_types(s: &mut String, idl: &Value) {
+    writeln!(s, "/// Types of the Gold Rush IDL").unwrap();
+    writeln!(s, "pub mod types {{").unwrap();
+    writeln!(s, "    use anchor_lang::prelude::*;\n").unwrap();
+
+    for t in idl["types"].as_array().expect("IDL types") {
+        let name = pascal_case(t["name"].as_str().expect("type name"));
+        let ty = &t["type"];
+        write_docs(s, "    ", t);
+        writeln!(
+            s,
+            "    #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]"
+        )
+        .unwrap();
+        match ty["kind"].as_str() {
+            Some("struct") => {
+                writeln!(s, "    pub struct {} {{", name).unwrap();
+                for f in ty["fields"].as_array().expect("struct fields") {
+                    write_docs(s, "        ", f);
+                    writeln!(
+                        s,
+                        "        pub {}: {},",
+                        snake_case(f["name"].as_str().expect("field name")),
+                        rust_type(&f["type"])
+                    )
+                    .unwrap();
+                }
+                writeln!(s, "    }}\n").unwrap();
+            }
+            Some("enum") => {
+                writeln!(s, "    pub enum {} {{", name).unwrap();
+                for v in ty["variants"].as_array().expect("enum variants") {
+                    let variant = pascal_case(v["name"].as_str().expect("variant name"));
+                    let fields = v["fields"].as_array().filter(|f| !f.is_empty());
+                    match fields {
+                        None => writeln!(s, "        {},", variant).unwrap(),
+                        // Named fields are objects, tuple fields are bare types
+                        Some(fields) if fields[0].get("name").is_some() => {
+                            let fields: Vec<String> = fields
+                                .iter()
+                                .map(|f| {
+                                    format!(
+                                        "{}: {}",
+                                        snake_case(f["name"].as_str().unwrap()),
+                                        rust_type(&f["type"])
+                                    )
+                                })
+                                .collect();
+                            writeln!(s, "        {} {{ {} }},", variant, fields.join(", "))
+                                .unwrap();
+                        }
+                        Some(fields) => {
+                            let fields: Vec<String> = fields.iter().map(rust_type).collect();
+                            writeln!(s, "        {}({}),", variant, fields.join(", ")).unwrap();
+                        }
+                    }
+                }
+                writeln!(s, "    }}\n").unwrap();
+            }
+            other => panic!("unsupported IDL type kind {:?} for {}", other, name),
+        }
+    }
+
+    for a in idl["accounts"].as_array().expect("IDL accounts") {
+        let name = pascal_case(a["name"].as_str().expect("account name"));
+        writeln!(s, "    impl {} {{", name).unwrap();
+        writeln!(s, "        /// Anchor account discriminator").unwrap();
+        writeln!(
+            s,
+            "        pub const DISCRIMINATOR: [u8; 8] = {};",
+            discriminator(a)
+        )
+        .unwrap();
+        writeln!(s, "    }}\n").unwrap();
+    }
+
+    writeln!(s, "}}\n").unwrap();
+}
+
+/// `instructions` module: one module per instruction with its discriminator, `Accounts`,
+/// `Args` (when it takes any) and an `instruction` builder
+fn gen_instructions(s: &mut String, idl: &Value) {
+    writeln!(s, "/// Instruction builders of the Gold Rush IDL").unwrap();
+    writeln!(s, "pub mod instructions {{").unwrap();
+
+    for ix in idl["instructions"].as_array().expect("IDL instructions") {
+        let ix_name = ix["name"].as_str().expect("instruction name");
+        let accounts = ix["accounts"].as_array().expect("instruction accounts");
+        let args = ix["args"].as_array().expect("instruction args");
+
+        writeln!(s, "    /// `{}`", ix_name).unwrap();
+        writeln!(s, "    pub mod {} {{", snake_case(ix_name)).unwrap();
+        writeln!(s, "        #[allow(unused_imports)]").unwrap();
+        writeln!(s, "        use super::super::types::*;").unwrap();
+        if !args.is_empty() {
+            writeln!(
+                s,
+                "        use anchor_lang::prelude::{{AnchorDeserialize, AnchorSerialize, borsh}};"
+            )
+            .unwrap();
+        }
+        writeln!(
+            s,
+            "        use solana_sdk::{{instruction::{{AccountMeta, Instruction}}, pubkey::Pubkey}};\n"
+        )
+        .unwrap();
+
+        writeln!(
+            s,
+            "        pub const DISCRIMINATOR: [u8; 8] = {};\n",
+            discriminator(ix)
+        )
+        .unwrap();
+
+        // Accounts, in IDL order
+        writeln!(s, "        #[derive(Clone, Debug)]").unwrap();
+        writeln!(s, "        pub struct Accounts {{").unwrap();
+        for a in accounts {
+            let name = snake_case(a["name"].as_str().expect("account name"));
+            let optional = a["optional"].as_bool().unwrap_or(false);
+            if let Some(address) = a["address"].as_str() {
+                writeln!(s, "            /// Usually `{}`", address).unwrap();
+            }
+            let ty = if optional { "Option<Pubkey>" } else { "Pubkey" };
+            writeln!(s, "            pub {}: {},", name, ty).unwrap();
+        }
+        writeln!(s, "        }}\n").unwrap();
+
+        writeln!(s, "        impl Accounts {{").unwrap();
+        writeln!(
+            s,
+            "            /// Account metas; a missing optional account is passed as the program id"
+        )
+        .unwrap();
+        // The program id only stands in for missing optional accounts
+        let has_optional = accounts
+            .iter()
+            .any(|a| a["optional"].as_bool() == Some(true));
+        writeln!(
+            s,
+            "            pub fn to_account_metas(&self, {}: &Pubkey) -> Vec<AccountMeta> {{",
+            if has_optional {
+                "program_id"
+            } else {
+                "_program_id"
+            }
+        )
+        .unwrap();
+        writeln!(s, "                vec![").unwrap();
+        for a in accounts {
+            let name = snake_case(a["name"].as_str().unwrap());
+            let writable = a["writable"].as_bool().unwrap_or(false);
+            let signer = a["signer"].as_bool().unwrap_or(false);
+            let optional = a["optional"].as_bool().unwrap_or(false);
+            let ctor = if writable { "new" } else { "new_readonly" };
+            if optional {
+                writeln!(
+                    s,
+                    "                    match self.{} {{ Some(key) => AccountMeta::{}(key, {}), None => AccountMeta::new_readonly(*program_id, false) }},",
+                    name, ctor, signer
+                )
+                .unwrap();
+            } else {
+                writeln!(
+                    s,
+                    "                    AccountMeta::{}(self.{}, {}),",
+                    ctor, name, signer
+                )
+                .unwrap();
+            }
+        }
+        writeln!(s, "                ]").unwrap();
+        writeln!(s, "            }}").unwrap();
+        writeln!(s, "        }}\n").unwrap();
+
+        if args.is_empty() {
+            writeln!(
+                s,
+                "        pub fn instruction(program_id: &Pubkey, accounts: &Accounts) -> Instruction {{"
+            )
+            .unwrap();
+            writeln!(s, "            Instruction {{").unwrap();
+            writeln!(s, "                program_id: *program_id,").unwrap();
+            writeln!(
+                s,
+                "                accounts: accounts.to_account_metas(program_id),"
+            )
+            .unwrap();
+            writeln!(s, "                data: DISCRIMINATOR.to_vec(),").unwrap();
+            writeln!(s, "            }}").unwrap();
+            writeln!(s, "        }}").unwrap();
+        } else {
+            writeln!(
+                s,
+                "        #[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug, PartialEq, Eq)]"
+            )
+            .unwrap();
+            writeln!(s, "        pub struct Args {{").unwrap();
+            // Args are borsh encoded with anchor, so they hold anchor's pubkey type
+            for a in args {
+                writeln!(
+                    s,
+                    "            pub {}: {},",
+                    snake_case(a["name"].as_str().expect("arg name")),
+                    rust_type_with(&a["type"], "anchor_lang::prelude::Pubkey")
+                )
+                .unwrap();
+            }
+            writeln!(s, "        }}\n").unwrap();
+
+            writeln!(s, "        impl Args {{").unwrap();
+            writeln!(
+                s,
+                "            /// Discriminator followed by the borsh encoded args"
+            )
+            .unwrap();
+            writeln!(s, "            pub fn data(&self) -> Vec<u8> {{").unwrap();
+            writeln!(s, "                let mut data = DISCRIMINATOR.to_vec();").unwrap();
+            writeln!(
+                s,
+                "                self.serialize(&mut data).expect(\"serialize {} args\");",
+                ix_name
+            )
+            .unwrap();
+            writeln!(s, "                data").unwrap();
+            writeln!(s, "            }}").unwrap();
+            writeln!(s, "        }}\n").unwrap();
+
+            writeln!(
+                s,
+                "        pub fn instruction(program_id: &Pubkey, accounts: &Accounts, args: &Args) -> Instruction {{"
+            )
+            .unwrap();
+            writeln!(s, "            Instruction {{").unwrap();
+            writeln!(s, "                program_id: *program_id,").unwrap();
+            writeln!(
+                s,
+                "                accounts: accounts.to_account_metas(program_id),"
+            )
+            .unwrap();
+            writeln!(s, "                data: args.data(),").unwrap();
+            writeln!(s, "            }}").unwrap();
+            writeln!(s, "        }}").unwrap();
+        }
+
+        writeln!(s, "    }}\n").unwrap();
+    }
+
+    writeln!(s, "}}").unwrap();
+}
+
+/// Emit `GoldRushError` from the IDL `errors` section
+fn gen_errors(idl: &Value) -> String {
+    let errors = idl["errors"].as_array().expect("IDL errors");
+
+    let mut s = String::new();
+    writeln!(s, "/// Gold Rush program errors (generated from the IDL)").unwrap();
+    writeln!(s, "#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]").unwrap();
+    writeln!(s, "pub enum GoldRushError {{").unwrap();
+    for e in errors {
+        let name = e["name"].as_str().expect("error name");
+        let code = e["code"].as_u64().expect("error code");
+        writeln!(s, "    {} = {},", pascal_case(name), code).unwrap();
+    }
+    writeln!(s, "}}\n").unwrap();
+
+    writeln!(s, "impl GoldRushError {{").unwrap();
+
+    writeln!(s, "    pub fn from_code(code: u32) -> Option<Self> {{").unwrap();
+    writeln!(s, "        match code {{").unwrap();
+    for e in errors {
+        let name = e["name"].as_str().unwrap();
+        let code = e["code"].as_u64().unwrap();
+        writeln!(
+            s,
+            "            {} => Some(Self::{}),",
+            code,
+            pascal_case(name)
+        )
+        .unwrap();
+    }
+    writeln!(s, "            _ => None,").unwrap();
+    writeln!(s, "        }}\n    }}\n").unwrap();
+
+    writeln!(
+        s,
+        "    pub fn code(&self) -> u32 {{\n        *self as u32\n    }}\n"
+    )
+    .unwrap();
+
+    writeln!(s, "    /// Error name as it appears in the IDL").unwrap();
+    writeln!(s, "    pub fn name(&self) -> &'static str {{").unwrap();
+    writeln!(s, "        match self {{").unwrap();
+    for e in errors {
+        let name = e["name"].as_str().unwrap();
+        writeln!(s, "            Self::{} => {:?},", pascal_case(name), name).unwrap();
+    }
+    writeln!(s, "        }}\n    }}\n").unwrap();
+
+    writeln!(s, "    pub fn msg(&self) -> &'static str {{").unwrap();
+    writeln!(s, "        match self {{").unwrap();
+    for e in errors {
+        let name = e["name"].as_str().unwrap();
+        let msg = e["msg"].as_str().unwrap_or("");
+        writeln!(s, "            Self::{} => {:?},", pascal_case(name), msg).unwrap();
+    }
+    writeln!(s, "        }}\n    }}").unwrap();
+
+    writeln!(s, "}}").unwrap();
+    s
+}
@@ -1,2 +1,8 @@
+pub mod alt;
 pub mod anchor;
+pub mod chunk;
+pub mod export;
+pub mod fee;
+pub mod pool;
+pub mod retry;
 pub mod rpc;
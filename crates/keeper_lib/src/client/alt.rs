@@ -0,0 +1,343 @@
+use anyhow::{Context, Result, bail};
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_address_lookup_table_interface::{
+    instruction::{
+        close_lookup_table, create_lookup_table, deactivate_lookup_table, extend_lookup_table,
+    },
+    program::id as lookup_table_program_id,
+    state::{AddressLookupTable, LOOKUP_TABLE_MAX_ADDRESSES, LookupTableMeta},
+};
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_commitment_config::CommitmentConfig;
+use solana_sdk::{message::AddressLookupTableAccount, pubkey::Pubkey, signature::Signature};
+use std::{
+    collections::{HashMap, HashSet},
+    sync::Mutex,
+    thread::sleep,
+    time::Duration,
+};
+use tracing::{debug, info, warn};
+
+use crate::client::rpc::{Rpc, send_tx_with_retry};
+use crate::storage::sqlite::{TxContext, clear_tx_context, set_tx_context};
+use crate::wallet::TxSigners;
+
+/// Addresses appended per extend transaction (keeps it well under the packet limit)
+const EXTEND_CHUNK: usize = 20;
+/// Offset of the authority in a lookup table account
+/// (type u32, deactivation slot u64, last extended slot u64, start index u8, option tag u8)
+const AUTHORITY_OFFSET: usize = 22;
+/// Slots a deactivated table has to wait before it can be closed (slot hashes length + 1)
+const DEACTIVATION_COOLDOWN_SLOTS: u64 = 513;
+/// How many times to poll for the slot after the last extension
+const WARMUP_POLLS: usize = 30;
+
+/// Address lookup tables owned by the keeper, one per round.
+///
+/// The round PDA is always the first address of its table, so the table of a round can be
+/// found again on-chain (authority + first address) after a restart.
+pub struct LookupTables {
+    tables: Mutex<HashMap<Pubkey, Pubkey>>,
+}
+
+impl LookupTables {
+    pub fn new() -> Self {
+        Self {
+            tables: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Lookup table of the round, if one exists and is still active
+    pub fn round_table(
+        &self,
+        rpc: &Rpc,
+        authority: &Pubkey,
+        round_pda: &Pubkey,
+    ) -> Result<Option<AddressLookupTableAccount>> {
+        let cached = self.tables.lock().unwrap().get(round_pda).copied();
+        let key = match cached {
+            Some(key) => key,
+            None => match find_round_table(rpc, authority, round_pda)? {
+                Some(key) => {
+                    self.tables.lock().unwrap().insert(*round_pda, key);
+                    key
+                }
+                None => return Ok(None),
+            },
+        };
+
+        let (table, meta) = fetch_table(rpc, &key)?;
+        if meta.deactivation_slot != u64::MAX {
+            self.tables.lock().unwrap().remove(round_pda);
+            return Ok(None);
+        }
+        Ok(Some(table))
+    }
+
+    /// Create the round's lookup table if needed and extend it with the missing addresses.
+    ///
+    /// Fails if the addresses don't fit in a single table; callers then fall back to legacy
+    /// transactions.
+    pub fn ensure_round_table(
+        &self,
+        rpc: &Rpc,
+        signers: &TxSigners,
+        round_id: u64,
+        round_pda: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> Result<AddressLookupTableAccount> {
+        let authority = signers.authority_pubkey();
+
+        let table = match self.round_table(rpc, &authority, round_pda)? {
+            Some(table) => table,
+            None => self.create_round_table(rpc, signers, round_id, round_pda)?,
+        };
+
+        let mut seen: HashSet<Pubkey> = table.addresses.iter().copied().collect();
+        let missing: Vec<Pubkey> = addresses
+            .iter()
+            .filter(|a| seen.insert(**a))
+            .copied()
+            .collect();
+        if missing.is_empty() {
+            return Ok(table);
+        }
+
+        if table.addresses.len() + missing.len() > LOOKUP_TABLE_MAX_ADDRESSES {
+            bail!(
+                "round {} needs {} lookup table addresses, max is {}",
+                round_id,
+                table.addresses.len() + missing.len(),
+                LOOKUP_TABLE_MAX_ADDRESSES
+            );
+        }
+
+        for (i, chunk) in missing.chunks(EXTEND_CHUNK).enumerate() {
+            let ix = extend_lookup_table(
+                table.key,
+                authority,
+                Some(signers.fee_payer_pubkey()),
+                chunk.to_vec(),
+            );
+            let range_start = table.addresses.len() + i * EXTEND_CHUNK;
+            set_tx_context(TxContext {
+                keeper_type: "alt".into(),
+                op: "extend_lookup_table".into(),
+                round_id: Some(round_id as i64),
+                group_id: None,
+                range_start: Some(range_start as i64),
+                range_end: Some((range_start + chunk.len() - 1) as i64),
+            });
+            let sig = send_tx_with_retry(rpc, signers, vec![ix]);
+            clear_tx_context();
+            let sig = sig.with_context(|| format!("extend lookup table {}", table.key))?;
+            debug!(lookup_table = %table.key, added = chunk.len(), tx_sig = %sig, "extended lookup table");
+        }
+
+        // Addresses are only usable once the slot they were added in has passed
+        let (table, meta) = fetch_table(rpc, &table.key)?;
+        wait_for_slot_after(rpc, meta.last_extended_slot)
+            .with_context(|| format!("lookup table {} did not warm up", table.key))?;
+        info!(round_id, lookup_table = %table.key, addresses = table.addresses.len(), "lookup table ready");
+
+        Ok(table)
+    }
+
+    /// Deactivate the round's lookup table once the round no longer needs it
+    pub fn deactivate_round_table(
+        &self,
+        rpc: &Rpc,
+        signers: &TxSigners,
+        round_id: u64,
+        round_pda: &Pubkey,
+    ) -> Result<Option<Signature>> {
+        let authority = signers.authority_pubkey();
+        let Some(table) = self.round_table(rpc, &authority, round_pda)? else {
+            return Ok(None);
+        };
+
+        set_tx_context(TxContext {
+            keeper_type: "alt".into(),
+            op: "deactivate_lookup_table".into(),
+            round_id: Some(round_id as i64),
+            ..Default::default()
+        });
+        let sig = send_tx_with_retry(
+            rpc,
+            signers,
+            vec![deactivate_lookup_table(table.key, authority)],
+        );
+        clear_tx_context();
+        let sig = sig.with_context(|| format!("deactivate lookup table {}", table.key))?;
+
+        self.tables.lock().unwrap().remove(round_pda);
+        info!(round_id, lookup_table = %table.key, tx_sig = %sig, "lookup table deactivated");
+        Ok(Some(sig))
+    }
+
+    /// Close the keeper's deactivated lookup tables whose cooldown elapsed, reclaiming rent
+    pub fn close_expired(&self, rpc: &Rpc, signers: &TxSigners) -> Result<Vec<Signature>> {
+        let authority = signers.authority_pubkey();
+        let current_slot = rpc.read("get_slot", |c| c.get_slot())?;
+
+        let mut sigs = Vec::new();
+        for (key, data) in authority_tables(rpc, &authority)? {
+            let table = match AddressLookupTable::deserialize(&data) {
+                Ok(table) => table,
+                Err(e) => {
+                    warn!(lookup_table = %key, error = ?e, "invalid lookup table account");
+                    continue;
+                }
+            };
+            let deactivation_slot = table.meta.deactivation_slot;
+            if deactivation_slot == u64::MAX
+                || current_slot <= deactivation_slot.saturating_add(DEACTIVATION_COOLDOWN_SLOTS)
+            {
+                continue;
+            }
+
+            set_tx_context(TxContext {
+                keeper_type: "alt".into(),
+                op: "close_lookup_table".into(),
+                ..Default::default()
+            });
+            let res = send_tx_with_retry(
+                rpc,
+                signers,
+                vec![close_lookup_table(
+                    key,
+                    authority,
+                    signers.fee_payer_pubkey(),
+                )],
+            );
+            clear_tx_context();
+            match res {
+                Ok(sig) => {
+                    info!(lookup_table = %key, tx_sig = %sig, "lookup table closed");
+                    sigs.push(sig);
+                }
+                Err(e) => warn!(lookup_table = %key, error = %e, "close lookup table failed"),
+            }
+        }
+
+        Ok(sigs)
+    }
+
+    fn create_round_table(
+        &self,
+        rpc: &Rpc,
+        signers: &TxSigners,
+        round_id: u64,
+        round_pda: &Pubkey,
+    ) -> Result<AddressLookupTableAccount> {
+        let authority = signers.authority_pubkey();
+        // The recent slot has to be in the slot hashes sysvar, so use a confirmed one
+        let recent_slot = rpc.read("get_slot", |c| {
+            c.get_slot_with_commitment(CommitmentConfig::confirmed())
+        })?;
+        // Rent comes from the fee payer; the authority owns the table so it survives a
+        // fee payer rotation
+        let fee_payer = signers.fee_payer_pubkey();
+        let (create_ix, key) = create_lookup_table(authority, fee_payer, recent_slot);
+        let extend_ix = extend_lookup_table(key, authority, Some(fee_payer), vec![*round_pda]);
+
+        set_tx_context(TxContext {
+            keeper_type: "alt".into(),
+            op: "create_lookup_table".into(),
+            round_id: Some(round_id as i64),
+            ..Default::default()
+        });
+        let sig = send_tx_with_retry(rpc, signers, vec![create_ix, extend_ix]);
+        clear_tx_context();
+        let sig = sig.with_context(|| format!("create lookup table for round {}", round_id))?;
+
+        self.tables.lock().unwrap().insert(*round_pda, key);
+        info!(round_id, lookup_table = %key, tx_sig = %sig, "lookup table created");
+
+        Ok(AddressLookupTableAccount {
+            key,
+            addresses: vec![*round_pda],
+        })
+    }
+}
+
+impl Default for LookupTables {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Whether every address is in the table
+pub fn covers(table: &AddressLookupTableAccount, addresses: &[Pubkey]) -> bool {
+    let held: HashSet<&Pubkey> = table.addresses.iter().collect();
+    addresses.iter().all(|a| held.contains(a))
+}
+
+/// Fetch a lookup table with its meta (`deactivation_slot` is `u64::MAX` while active)
+fn fetch_table(rpc: &Rpc, key: &Pubkey) -> Result<(AddressLookupTableAccount, LookupTableMeta)> {
+    let acc = rpc
+        .read("get_account", |c| c.get_account(key))
+        .with_context(|| format!("Failed to fetch lookup table {}", key))?;
+    let table = AddressLookupTable::deserialize(&acc.data)
+        .map_err(|e| anyhow::anyhow!("Failed to deserialize lookup table {}: {:?}", key, e))?;
+
+    Ok((
+        AddressLookupTableAccount {
+            key: *key,
+            addresses: table.addresses.to_vec(),
+        },
+        table.meta,
+    ))
+}
+
+/// Active lookup table owned by the authority whose first address is the round PDA
+///
+/// Deactivated tables of the round (released, waiting to be closed) are ignored.
+fn find_round_table(rpc: &Rpc, authority: &Pubkey, round_pda: &Pubkey) -> Result<Option<Pubkey>> {
+    let tables = authority_tables(rpc, authority)?;
+    Ok(tables.into_iter().find_map(|(key, data)| {
+        let table = AddressLookupTable::deserialize(&data).ok()?;
+        let active = table.meta.deactivation_slot == u64::MAX;
+        (active && table.addresses.first() == Some(round_pda)).then_some(key)
+    }))
+}
+
+/// Raw lookup table accounts owned by the authority
+fn authority_tables(rpc: &Rpc, authority: &Pubkey) -> Result<Vec<(Pubkey, Vec<u8>)>> {
+    let program_id = lookup_table_program_id();
+    let cfg = RpcProgramAccountsConfig {
+        filters: Some(vec![RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+            AUTHORITY_OFFSET,
+            authority.to_bytes().to_vec(),
+        ))]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc
+        .read("get_program_accounts", |c| {
+            c.get_program_accounts_with_config(&program_id, cfg.clone())
+        })
+        .context("Failed to list lookup tables")?;
+
+    Ok(accounts
+        .into_iter()
+        .map(|(key, acc)| (key, acc.data))
+        .collect())
+}
+
+/// Wait until the cluster moved past the given slot
+fn wait_for_slot_after(rpc: &Rpc, slot: u64) -> Result<()> {
+    for _ in 0..WARMUP_POLLS {
+        if rpc.read("get_slot", |c| c.get_slot())? > slot {
+            return Ok(());
+        }
+        sleep(Duration::from_millis(400));
+    }
+    bail!("slot {} not reached", slot)
+}
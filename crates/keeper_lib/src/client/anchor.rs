@@ -1,37 +1,52 @@
-use anchor_lang::AnchorDeserialize;
+use anchor_lang::{AnchorDeserialize, prelude::borsh};
 use anyhow::{Context, Result, bail};
 use hex;
-use sha2::{Digest, Sha256};
-use solana_client::rpc_client::RpcClient;
+use solana_account_decoder_client_types::UiAccountEncoding;
+use solana_client::{
+    rpc_config::{RpcAccountInfoConfig, RpcProgramAccountsConfig},
+    rpc_filter::{Memcmp, RpcFilterType},
+};
+use solana_clock::{Clock, sysvar as clock_sysvar};
 use solana_sdk::{
+    account::Account,
     instruction::{AccountMeta, Instruction},
+    message::AddressLookupTableAccount,
     pubkey::Pubkey,
-    signature::{Keypair, Signature, Signer},
+    signature::Signature,
+};
+use std::{
+    ops::RangeInclusive,
+    slice,
+    sync::atomic::{AtomicU64, Ordering},
+    time::Instant,
 };
 
+use crate::errors::{DecodeError, StaleFeed, StalePriceError};
 use crate::storage::sqlite::{TxContext, clear_tx_context, set_tx_context};
+use crate::wallet::TxSigners;
 use crate::{
-    client::rpc::{Rpc, send_tx_with_retry},
+    client::{
+        chunk::{ChunkItem, send_chunked},
+        rpc::{Rpc, send_tx_with_retry},
+    },
+    idl::instructions as ix,
     pda::{
         derive_asset_pda, derive_bet_pda, derive_config_pda, derive_group_asset_pda,
-        derive_round_pda,
+        derive_round_pda, derive_round_vault_pda,
     },
     types::{
-        asset_account::AssetAccount, config_account::ConfigAccount, enums::MarketType,
-        group_asset_account::GroupAssetAccount, round_account::RoundAccount,
+        asset_account::AssetAccount,
+        bet::BetAccount,
+        config_account::ConfigAccount,
+        enums::{BetStatus, MarketType, RoundStatus},
+        group_asset_account::GroupAssetAccount,
+        price_update::PriceUpdateAccount,
+        round_account::RoundAccount,
     },
 };
-use tracing::{debug, info};
+use tracing::{debug, info, warn};
 
-/// Generate a 8-byte sighash for a global instruction
-fn sighash_global(ix_name: &str) -> [u8; 8] {
-    let mut hasher = Sha256::new();
-    hasher.update(format!("{}:{}", "global", ix_name));
-    let hash = hasher.finalize();
-    let mut sighash = [0u8; 8];
-    sighash.copy_from_slice(&hash[..8]);
-    sighash
-}
+static ROUND_DECODE_FAILURES: AtomicU64 = AtomicU64::new(0);
 
 /// Get the price feed account for a given feed ID
 pub fn get_price_feed_account(
@@ -52,14 +67,56 @@ pub fn get_price_feed_account(
     Ok(pda)
 }
 
+/// Decode an Anchor account, checking its discriminator against the IDL
+pub fn decode_account<T: AnchorDeserialize>(
+    account: &'static str,
+    discriminator: &[u8; 8],
+    data: &[u8],
+) -> Result<T, DecodeError> {
+    let Some((found, mut body)) = data.split_first_chunk::<8>() else {
+        return Err(DecodeError::Truncated {
+            account,
+            len: data.len(),
+        });
+    };
+    if found != discriminator {
+        return Err(DecodeError::WrongType {
+            account,
+            expected: *discriminator,
+            found: *found,
+        });
+    }
+
+    T::deserialize(&mut body).map_err(|err| DecodeError::Layout {
+        account,
+        reason: err.to_string(),
+    })
+}
+
 /// Fetch and deserialize Config account
-pub fn get_config_account(client: &RpcClient, program_id: &Pubkey) -> Result<ConfigAccount> {
+pub fn get_config_account(rpc: &Rpc, program_id: &Pubkey) -> Result<ConfigAccount> {
     let config_pda = derive_config_pda(program_id);
 
-    let acc = client
-        .get_account(&config_pda)
+    let acc = rpc
+        .read("get_account", |c| c.get_account(&config_pda))
         .with_context(|| format!("Failed to fetch config account {}", config_pda))?;
 
+    decode_config_account(&acc, program_id)
+}
+
+/// Async `get_config_account` on the nonblocking client
+pub async fn get_config_account_async(rpc: &Rpc, program_id: &Pubkey) -> Result<ConfigAccount> {
+    let config_pda = derive_config_pda(program_id);
+
+    let acc = rpc
+        .read_async("get_account", |c| c.get_account(&config_pda))
+        .await
+        .with_context(|| format!("Failed to fetch config account {}", config_pda))?;
+
+    decode_config_account(&acc, program_id)
+}
+
+fn decode_config_account(acc: &Account, program_id: &Pubkey) -> Result<ConfigAccount> {
     if acc.owner != *program_id {
         bail!(
             "Config owner mismatch. expected={}, got={}",
@@ -68,20 +125,30 @@ pub fn get_config_account(client: &RpcClient, program_id: &Pubkey) -> Result<Con
         );
     }
 
-    let data = acc.data;
-    if data.len() < 8 {
-        bail!("Config account data too short");
-    }
-
-    let mut cursor = &data[8..];
-    let cfg = ConfigAccount::deserialize(&mut cursor).context("Failed to deserialize Config")?;
+    let cfg = decode_account("Config", &ConfigAccount::DISCRIMINATOR, &acc.data)
+        .context("Failed to deserialize Config")?;
 
     Ok(cfg)
 }
 
 /// Fetch and deserialize multiple Round accounts by their IDs
-pub fn get_rounds_by_ids(
-    client: &RpcClient,
+pub fn get_rounds_by_ids(rpc: &Rpc, program_id: &Pubkey, ids: &[u64]) -> Result<Vec<RoundAccount>> {
+    let pubkeys: Vec<Pubkey> = ids
+        .iter()
+        .map(|&id| derive_round_pda(program_id, id))
+        .collect();
+    let accounts = rpc
+        .read("get_multiple_accounts", |c| {
+            c.get_multiple_accounts(&pubkeys)
+        })
+        .context("Failed to get multiple round accounts")?;
+
+    Ok(decode_round_accounts(accounts, ids, program_id))
+}
+
+/// Async `get_rounds_by_ids` on the nonblocking client
+pub async fn get_rounds_by_ids_async(
+    rpc: &Rpc,
     program_id: &Pubkey,
     ids: &[u64],
 ) -> Result<Vec<RoundAccount>> {
@@ -89,39 +156,106 @@ pub fn get_rounds_by_ids(
         .iter()
         .map(|&id| derive_round_pda(program_id, id))
         .collect();
-    let accounts = client
-        .get_multiple_accounts(&pubkeys)
+    let accounts = rpc
+        .read_async("get_multiple_accounts", |c| {
+            c.get_multiple_accounts(&pubkeys)
+        })
+        .await
         .context("Failed to get multiple round accounts")?;
 
+    Ok(decode_round_accounts(accounts, ids, program_id))
+}
+
+/// Round accounts that failed to decode since startup
+pub fn round_decode_failures() -> u64 {
+    ROUND_DECODE_FAILURES.load(Ordering::Relaxed)
+}
+
+fn decode_round_accounts(
+    accounts: Vec<Option<Account>>,
+    ids: &[u64],
+    program_id: &Pubkey,
+) -> Vec<RoundAccount> {
     let mut out = Vec::new();
-    for (_, acc_opt) in pubkeys.into_iter().zip(accounts.into_iter()) {
-        if let Some(acc) = acc_opt {
-            if acc.owner != *program_id {
-                continue;
-            }
+    for (&round_id, acc) in ids.iter().zip(accounts) {
+        // Not created yet
+        let Some(acc) = acc else {
+            continue;
+        };
 
-            if acc.data.len() < 8 {
-                continue;
+        if acc.owner != *program_id {
+            let failures = ROUND_DECODE_FAILURES.fetch_add(1, Ordering::Relaxed) + 1;
+            warn!(event = "round_decode_failed", round_id, owner = %acc.owner, failures, "round account owner mismatch, skipping");
+            continue;
+        }
+
+        match decode_account("Round", &RoundAccount::DISCRIMINATOR, &acc.data) {
+            Ok(round) => out.push(round),
+            Err(err) => {
+                let failures = ROUND_DECODE_FAILURES.fetch_add(1, Ordering::Relaxed) + 1;
+                warn!(event = "round_decode_failed", round_id, error = %err, failures, "failed to decode round account, skipping");
             }
+        }
+    }
+
+    out
+}
+
+/// Offset of `Round::status` (discriminator, id, start/end/cutoff times, vault, vault bump,
+/// market type)
+const ROUND_STATUS_OFFSET: usize = 8 + 8 + 8 + 8 + 8 + 32 + 1 + 1;
 
-            let mut cursor = &acc.data[8..];
-            if let Ok(round) = RoundAccount::deserialize(&mut cursor) {
-                out.push(round);
+/// Fetch every Round account in the given status with `getProgramAccounts`
+pub async fn get_rounds_by_status_async(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    status: &RoundStatus,
+) -> Result<Vec<RoundAccount>> {
+    let status_byte = borsh::to_vec(status).context("Failed to serialize round status")?;
+    let cfg = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(
+                0,
+                RoundAccount::DISCRIMINATOR.to_vec(),
+            )),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(ROUND_STATUS_OFFSET, status_byte)),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc
+        .read_async("get_program_accounts", |c| {
+            c.get_program_accounts_with_config(program_id, cfg.clone())
+        })
+        .await
+        .with_context(|| format!("Failed to list {:?} rounds", status))?;
+
+    let mut out: Vec<RoundAccount> = Vec::new();
+    for (round_pda, acc) in accounts {
+        match decode_account("Round", &RoundAccount::DISCRIMINATOR, &acc.data) {
+            Ok(round) => out.push(round),
+            Err(err) => {
+                let failures = ROUND_DECODE_FAILURES.fetch_add(1, Ordering::Relaxed) + 1;
+                warn!(event = "round_decode_failed", round = %round_pda, error = %err, failures, "failed to decode round account, skipping");
             }
         }
     }
+    out.sort_by_key(|round| round.id);
 
     Ok(out)
 }
 
 /// Fetch and deserialize GroupAsset account
 pub fn get_group_asset_account(
-    client: &RpcClient,
+    rpc: &Rpc,
     program_id: &Pubkey,
     group_asset_pda: &Pubkey,
 ) -> Result<GroupAssetAccount> {
-    let acc = client
-        .get_account(&group_asset_pda)
+    let acc = rpc
+        .read("get_account", |c| c.get_account(group_asset_pda))
         .with_context(|| format!("Failed to get group asset account {}", group_asset_pda))?;
 
     if acc.owner != *program_id {
@@ -132,26 +266,20 @@ pub fn get_group_asset_account(
         );
     }
 
-    let data = acc.data;
-    if data.len() < 8 {
-        bail!("Group asset account data too short");
-    }
-
-    let mut cursor = &data[8..];
-    let group_asset = GroupAssetAccount::deserialize(&mut cursor)
-        .context("Failed to deserialize GroupAssetAccount")?;
+    let group_asset = decode_account("GroupAsset", &GroupAssetAccount::DISCRIMINATOR, &acc.data)
+        .with_context(|| format!("Failed to deserialize group asset {}", group_asset_pda))?;
 
     Ok(group_asset)
 }
 
 /// Fetch and deserialize Asset account
 pub fn get_asset_account(
-    client: &RpcClient,
+    rpc: &Rpc,
     asset_pda: &Pubkey,
     program_id: &Pubkey,
 ) -> Result<AssetAccount> {
-    let acc = client
-        .get_account(asset_pda)
+    let acc = rpc
+        .read("get_account", |c| c.get_account(asset_pda))
         .context("Failed to get asset account")?;
 
     if acc.owner != *program_id {
@@ -162,58 +290,480 @@ pub fn get_asset_account(
         );
     }
 
-    let data = acc.data;
-    if data.len() < 8 {
-        bail!("Asset account data too short");
+    let asset = decode_account("Asset", &AssetAccount::DISCRIMINATOR, &acc.data)
+        .with_context(|| format!("Failed to deserialize asset {}", asset_pda))?;
+
+    Ok(asset)
+}
+
+/// Max accounts per `getMultipleAccounts` request
+const MAX_MULTIPLE_ACCOUNTS: usize = 100;
+
+/// Fetch and decode accounts of one type in `getMultipleAccounts` batches, in order
+///
+/// Fails if any of them is missing, owned by another program or does not decode.
+fn get_accounts_batched<T: AnchorDeserialize>(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    account: &'static str,
+    discriminator: &[u8; 8],
+    pubkeys: &[Pubkey],
+) -> Result<Vec<T>> {
+    let mut out = Vec::with_capacity(pubkeys.len());
+    for batch in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc
+            .read("get_multiple_accounts", |c| c.get_multiple_accounts(batch))
+            .with_context(|| format!("Failed to get multiple {} accounts", account))?;
+
+        for (pubkey, acc) in batch.iter().zip(accounts) {
+            let acc = acc.with_context(|| format!("{} account {} not found", account, pubkey))?;
+            if acc.owner != *program_id {
+                bail!(
+                    "{} owner mismatch for {}. expected={}, got={}",
+                    account,
+                    pubkey,
+                    program_id,
+                    acc.owner
+                );
+            }
+            let decoded = decode_account(account, discriminator, &acc.data)
+                .with_context(|| format!("Failed to deserialize {} {}", account, pubkey))?;
+            out.push(decoded);
+        }
     }
 
-    let mut cursor = &data[8..];
-    let asset =
-        AssetAccount::deserialize(&mut cursor).context("Failed to deserialize AssetAccount")?;
+    Ok(out)
+}
 
-    Ok(asset)
+/// Fetch every GroupAsset account of a round with their PDAs, ordered by group id
+pub fn get_group_asset_accounts(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    round_pda: &Pubkey,
+    total_groups: u64,
+) -> Result<Vec<(Pubkey, GroupAssetAccount)>> {
+    let pdas: Vec<Pubkey> = (1..=total_groups)
+        .map(|group_id| derive_group_asset_pda(program_id, round_pda, group_id))
+        .collect();
+    let groups = get_accounts_batched(
+        rpc,
+        program_id,
+        "GroupAsset",
+        &GroupAssetAccount::DISCRIMINATOR,
+        &pdas,
+    )?;
+
+    Ok(pdas.into_iter().zip(groups).collect())
+}
+
+/// Fetch Asset accounts by PDA, in the same order
+pub fn get_asset_accounts(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    asset_pdas: &[Pubkey],
+) -> Result<Vec<AssetAccount>> {
+    get_accounts_batched(
+        rpc,
+        program_id,
+        "Asset",
+        &AssetAccount::DISCRIMINATOR,
+        asset_pdas,
+    )
+}
+
+/// Fetch PriceUpdateV2 accounts of push oracle price feeds, in the same order
+///
+/// The feed PDAs derive from the push oracle program, but their data is written through the
+/// Pyth receiver program, which owns them.
+pub fn get_price_update_accounts(
+    rpc: &Rpc,
+    pyth_receiver_program_id: &Pubkey,
+    price_feeds: &[Pubkey],
+) -> Result<Vec<PriceUpdateAccount>> {
+    get_accounts_batched(
+        rpc,
+        pyth_receiver_program_id,
+        "PriceUpdateV2",
+        &PriceUpdateAccount::DISCRIMINATOR,
+        price_feeds,
+    )
+}
+
+/// Unix timestamp of the on-chain clock (the clock sysvar)
+pub fn get_onchain_time(rpc: &Rpc) -> Result<i64> {
+    let acc = rpc
+        .read("get_account", |c| c.get_account(&clock_sysvar::ID))
+        .context("Failed to get clock sysvar")?;
+    let clock: Clock = bincode::deserialize(&acc.data).context("Failed to deserialize clock")?;
+
+    Ok(clock.unix_timestamp)
+}
+
+/// Fail with `StalePriceError` if a feed's latest update is older than `max_age_secs`
+///
+/// Ages are measured against the on-chain clock like the program does, so a feed the program
+/// would reject is caught before paying for the transaction.
+pub fn ensure_fresh_price_feeds(
+    rpc: &Rpc,
+    price_feeds: &[Pubkey],
+    max_age_secs: u64,
+    pyth_receiver_program_id: &Pubkey,
+) -> Result<()> {
+    if price_feeds.is_empty() {
+        return Ok(());
+    }
+
+    let updates = get_price_update_accounts(rpc, pyth_receiver_program_id, price_feeds)?;
+    let now = get_onchain_time(rpc)?;
+    let feeds: Vec<StaleFeed> = price_feeds
+        .iter()
+        .zip(updates)
+        .filter_map(|(price_feed_account, update)| {
+            let message = update.price_message;
+            let age_secs = now - message.publish_time;
+            (age_secs > max_age_secs as i64).then_some(StaleFeed {
+                price_feed_account: *price_feed_account,
+                feed_id: message.feed_id,
+                publish_time: message.publish_time,
+                age_secs,
+            })
+        })
+        .collect();
+    if !feeds.is_empty() {
+        return Err(StalePriceError {
+            max_age_secs,
+            feeds,
+        }
+        .into());
+    }
+
+    debug!(feeds = price_feeds.len(), now, "price feeds fresh");
+    Ok(())
+}
+
+/// Offset of `Bet::round` (after the discriminator and `id`)
+const BET_ROUND_OFFSET: usize = 8 + 8;
+
+/// Offset of `Bet::bettor`
+const BET_BETTOR_OFFSET: usize = BET_ROUND_OFFSET + 32;
+/// Fetch and deserialize a round's Bet accounts by id; closed and foreign-owned bets are left out
+/// Fetch and deserialize a round's Bet accounts by id; closed bets are left out
+pub fn get_bets_by_ids(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    round_pda: &Pubkey,
+    ids: RangeInclusive<u64>,
+) -> Result<Vec<BetAccount>> {
+    let pubkeys: Vec<Pubkey> = ids
+        .map(|bet_id| derive_bet_pda(program_id, round_pda, bet_id))
+        .collect();
+
+    let mut out = Vec::new();
+    for batch in pubkeys.chunks(MAX_MULTIPLE_ACCOUNTS) {
+        let accounts = rpc
+            .read("get_multiple_accounts", |c| c.get_multiple_accounts(batch))
+            .context("Failed to get multiple bet accounts")?;
+
+        for (bet_pda, acc) in batch.iter().zip(accounts) {
+            // Closed (claimed or withdrawn)
+            let Some(acc) = acc else {
+                continue;
+            };
+            if acc.owner != *program_id {
+                warn!(bet = %bet_pda, owner = %acc.owner, "bet account owner mismatch, skipping");
+                continue;
+            }
+            let bet = decode_account("Bet", &BetAccount::DISCRIMINATOR, &acc.data)
+                .with_context(|| format!("Failed to deserialize bet {}", bet_pda))?;
+            out.push(bet);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Fetch every open Bet account of a round, ordered by id
+pub fn get_bets_by_round(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    round_pda: &Pubkey,
+) -> Result<Vec<BetAccount>> {
+    get_bets_matching(rpc, program_id, BET_ROUND_OFFSET, round_pda)
+        .with_context(|| format!("Failed to list bets of round {}", round_pda))
+}
+
+/// Fetch every open Bet account of a bettor, ordered by id
+pub fn get_bets_by_bettor(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    bettor: &Pubkey,
+) -> Result<Vec<BetAccount>> {
+    get_bets_matching(rpc, program_id, BET_BETTOR_OFFSET, bettor)
+        .with_context(|| format!("Failed to list bets of bettor {}", bettor))
+}
+
+/// Bet accounts whose pubkey field at `offset` equals `key`
+fn get_bets_matching(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    offset: usize,
+    key: &Pubkey,
+) -> Result<Vec<BetAccount>> {
+    let cfg = RpcProgramAccountsConfig {
+        filters: Some(vec![
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(0, BetAccount::DISCRIMINATOR.to_vec())),
+            RpcFilterType::Memcmp(Memcmp::new_raw_bytes(offset, key.to_bytes().to_vec())),
+        ]),
+        account_config: RpcAccountInfoConfig {
+            encoding: Some(UiAccountEncoding::Base64),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let accounts = rpc.read("get_program_accounts", |c| {
+        c.get_program_accounts_with_config(program_id, cfg.clone())
+    })?;
+
+    let mut out = accounts
+        .into_iter()
+        .map(|(bet_pda, acc)| {
+            decode_account("Bet", &BetAccount::DISCRIMINATOR, &acc.data)
+                .with_context(|| format!("Failed to deserialize bet {}", bet_pda))
+        })
+        .collect::<Result<Vec<BetAccount>>>()?;
+    out.sort_by_key(|bet| (bet.round.to_bytes(), bet.id));
+
+    Ok(out)
+}
+
+/// Group asset, asset and price feed accounts of a group battle round, in lookup table order
+pub fn group_lookup_addresses(
+    rpc: &Rpc,
+    round_pda: &Pubkey,
+    round: &RoundAccount,
+    push_oracle_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Pubkey>> {
+    let started = Instant::now();
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, round.total_groups)?;
+    let asset_pdas: Vec<Vec<Pubkey>> = groups
+        .iter()
+        .map(|(group_asset_pda, group_asset)| {
+            (1..=group_asset.total_assets)
+                .map(|asset_id| derive_asset_pda(program_id, group_asset_pda, asset_id))
+                .collect()
+        })
+        .collect();
+    let assets = get_asset_accounts(rpc, program_id, &asset_pdas.concat())?;
+    log_fetch_latency(
+        round.id,
+        "group_lookup_table",
+        groups.len() + assets.len(),
+        started,
+    );
+
+    let mut assets = assets.into_iter();
+    let mut out = Vec::new();
+    for ((group_asset_pda, _), asset_pdas) in groups.iter().zip(asset_pdas) {
+        out.push(*group_asset_pda);
+        for (asset_pda, asset) in asset_pdas.into_iter().zip(assets.by_ref()) {
+            out.push(asset_pda);
+            out.push(get_price_feed_account(
+                0,
+                &hex::encode(asset.feed_id),
+                push_oracle_program_id,
+            )?);
+        }
+    }
+
+    Ok(out)
+}
+
+/// Bet accounts of a round, in lookup table order
+pub fn bet_lookup_addresses(
+    round_pda: &Pubkey,
+    round: &RoundAccount,
+    program_id: &Pubkey,
+) -> Vec<Pubkey> {
+    (1..=round.total_bets)
+        .map(|bet_id| derive_bet_pda(program_id, round_pda, bet_id))
+        .collect()
 }
 
 /// Start a round
+///
+/// A single asset round is not started while its gold price feed is stale (`StalePriceError`).
 pub fn start_round(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     gold_price_feed: Option<&Pubkey>,
+    max_price_age_secs: u64,
+    pyth_receiver_program_id: &Pubkey,
     system_program_id: &Pubkey,
     program_id: &Pubkey,
 ) -> Result<Signature> {
-    let data = sighash_global("start_round").to_vec();
-
-    let accounts = vec![
-        AccountMeta::new(payer.pubkey(), true),
-        AccountMeta::new(*config_pda, false),
-        AccountMeta::new(*round_pda, false),
-        AccountMeta::new_readonly(*gold_price_feed.unwrap_or(&program_id), false), // use program id if no gold price feed is provided
-        AccountMeta::new_readonly(*system_program_id, false),
-    ];
-
-    let instruction = Instruction {
-        data,
-        accounts,
-        program_id: *program_id,
-    };
+    if let Some(gold_price_feed) = gold_price_feed {
+        ensure_fresh_price_feeds(
+            rpc,
+            slice::from_ref(gold_price_feed),
+            max_price_age_secs,
+            pyth_receiver_program_id,
+        )?;
+    }
 
-    let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
+    // Without a gold price feed the program id stands in for the optional account
+    let instruction = ix::start_round::instruction(
+        program_id,
+        &ix::start_round::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            price_update: gold_price_feed.copied(),
+            system_program: *system_program_id,
+        },
+    );
+
+    let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec())?;
 
     Ok(sig)
 }
 
+/// Asset + price feed of every asset of each group, for the capture price instructions
+///
+/// The assets of all groups are fetched together, the items come back per group.
+fn asset_price_items(
+    rpc: &Rpc,
+    groups: &[(Pubkey, u64)],
+    push_oracle_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Vec<Vec<ChunkItem>>> {
+    let asset_pdas: Vec<Pubkey> = groups
+        .iter()
+        .flat_map(|(group_asset_pda, total_assets)| {
+            (1..=*total_assets)
+                .map(move |asset_id| derive_asset_pda(program_id, group_asset_pda, asset_id))
+        })
+        .collect();
+    let mut assets = asset_pdas
+        .iter()
+        .zip(get_asset_accounts(rpc, program_id, &asset_pdas)?);
+
+    let mut out = Vec::with_capacity(groups.len());
+    for (_, total_assets) in groups {
+        let mut items = Vec::with_capacity(*total_assets as usize);
+        for (asset_id, (asset_pda, asset)) in (1..=*total_assets).zip(assets.by_ref()) {
+            let price_feed_account =
+                get_price_feed_account(0, &hex::encode(asset.feed_id), push_oracle_program_id)?;
+            items.push(ChunkItem {
+                id: asset_id,
+                accounts: vec![
+                    AccountMeta::new(*asset_pda, false),
+                    AccountMeta::new_readonly(price_feed_account, false),
+                ],
+            });
+        }
+        out.push(items);
+    }
+    Ok(out)
+}
+
+/// Every asset of a group, for the finalize group assets instructions
+fn asset_items(group_asset_pda: &Pubkey, total_assets: u64, program_id: &Pubkey) -> Vec<ChunkItem> {
+    (1..=total_assets)
+        .map(|asset_id| ChunkItem {
+            id: asset_id,
+            accounts: vec![AccountMeta::new(
+                derive_asset_pda(program_id, group_asset_pda, asset_id),
+                false,
+            )],
+        })
+        .collect()
+}
+
+/// Group assets of the round that hold assets, for the finalize groups instructions
+fn group_items(
+    rpc: &Rpc,
+    round_pda: &Pubkey,
+    total_groups: u64,
+    program_id: &Pubkey,
+) -> Result<Vec<ChunkItem>> {
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, total_groups)?;
+    let mut items = Vec::new();
+    for (group_id, (group_asset_pda, group)) in (1u64..).zip(groups) {
+        if group.total_assets == 0 {
+            debug!(group_id, "group has no assets");
+            continue;
+        }
+        items.push(ChunkItem {
+            id: group_id,
+            accounts: vec![AccountMeta::new_readonly(group_asset_pda, false)],
+        });
+    }
+    Ok(items)
+}
+
+/// Log how long a round phase spent fetching accounts before its first transaction
+fn log_fetch_latency(round_id: u64, phase: &str, accounts: usize, started: Instant) {
+    let fetch_ms = started.elapsed().as_millis() as u64;
+    info!(
+        round_id,
+        phase, accounts, fetch_ms, "fetched round accounts"
+    );
+}
+
+/// Pending bets of the round, for the settle instructions
+///
+/// Bets withdrawn before settlement (closed) are skipped, and so are bets already settled
+/// by a chunk that landed before a retry.
+fn bet_items(
+    rpc: &Rpc,
+    round_pda: &Pubkey,
+    total_bets: u64,
+    program_id: &Pubkey,
+) -> Result<Vec<ChunkItem>> {
+    if total_bets == 0 {
+        return Ok(Vec::new());
+    }
+
+    let bets = get_bets_by_ids(rpc, program_id, round_pda, 1..=total_bets)?;
+    let closed = total_bets - bets.len() as u64;
+    let bets: Vec<BetAccount> = bets
+        .into_iter()
+        .filter(|bet| bet.status == BetStatus::Pending)
+        .collect();
+    let settled = total_bets - closed - bets.len() as u64;
+    if closed > 0 || settled > 0 {
+        debug!(round = %round_pda, total_bets, closed, settled, "skipping closed and settled bets");
+    }
+
+    Ok(bets
+        .iter()
+        .map(|bet| ChunkItem {
+            id: bet.id,
+            accounts: vec![AccountMeta::new(
+                derive_bet_pda(program_id, round_pda, bet.id),
+                false,
+            )],
+        })
+        .collect())
+}
+
 /// Capture the start price for a group
+///
+/// Nothing is sent while any of the price feeds is stale (`StalePriceError`).
 pub fn capture_start_price(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
+    max_price_age_secs: u64,
     push_oracle_program_id: &Pubkey,
+    pyth_receiver_program_id: &Pubkey,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -224,18 +774,16 @@ pub fn capture_start_price(
         bail!("capture_start_price requires at least one group");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let data = sighash_global("capture_start_price").to_vec();
-
     info!(round_pda = %round_pda, "capturing start price");
 
-    for group_id in 1..=round.total_groups {
-        let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id);
-        debug!(group_asset_pda = %group_asset_pda, "capturing start price for group");
-        let group_asset = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
+    let started = Instant::now();
+    let mut pending = Vec::new();
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, round.total_groups)?;
+    for (group_id, (group_asset_pda, group_asset)) in (1u64..).zip(groups) {
         if group_asset.total_assets < 1 {
             debug!(group_id, "group has no assets");
             continue;
@@ -246,70 +794,69 @@ pub fn capture_start_price(
             continue;
         }
 
-        let base_accounts: Vec<AccountMeta> = vec![
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new(*config_pda, false),
-            AccountMeta::new(*round_pda, false),
-            AccountMeta::new(group_asset_pda, false),
-            AccountMeta::new_readonly(*system_program_id, false),
-        ];
-
-        let per_asset_accounts = 2;
-        let max_assets_per_batch = (max_remaining_accounts / per_asset_accounts).max(1);
-        let total_assets = group_asset.total_assets as usize;
-        let mut start_asset = 1usize;
-        while start_asset <= total_assets {
-            let end_asset = (start_asset + max_assets_per_batch - 1).min(total_assets);
-            debug!(
-                start_asset,
-                end_asset, max_assets_per_batch, "capturing start price assets chunk"
-            );
-
-            let mut remaining_accounts: Vec<AccountMeta> =
-                Vec::with_capacity((end_asset - start_asset + 1) * per_asset_accounts);
-            for asset_id in start_asset..=end_asset {
-                let asset_pda = derive_asset_pda(program_id, &group_asset_pda, asset_id as u64);
-                remaining_accounts.push(AccountMeta {
-                    pubkey: asset_pda,
-                    is_signer: false,
-                    is_writable: true,
-                });
-
-                let asset = get_asset_account(rpc.client(), &asset_pda, program_id)?;
-                let price_feed_account =
-                    get_price_feed_account(0, &hex::encode(asset.feed_id), push_oracle_program_id)?;
-                remaining_accounts.push(AccountMeta {
-                    pubkey: price_feed_account,
-                    is_signer: false,
-                    is_writable: false,
-                });
-            }
-
-            let mut accounts = base_accounts.clone();
-            accounts.reserve(remaining_accounts.len());
-            accounts.extend(remaining_accounts.into_iter());
-
-            let instruction = Instruction {
-                data: data.clone(),
-                accounts,
-                program_id: *program_id,
-            };
+        pending.push((group_id, group_asset_pda, group_asset.total_assets));
+    }
+    let capture_groups: Vec<(Pubkey, u64)> = pending
+        .iter()
+        .map(|(_, group_asset_pda, total_assets)| (*group_asset_pda, *total_assets))
+        .collect();
+    let items_by_group =
+        asset_price_items(rpc, &capture_groups, push_oracle_program_id, program_id)?;
+    let accounts = round.total_groups as usize
+        + capture_groups
+            .iter()
+            .map(|(_, n)| *n as usize)
+            .sum::<usize>();
+    log_fetch_latency(round.id, "capture_start_price", accounts, started);
+
+    let mut price_feeds: Vec<Pubkey> = items_by_group
+        .iter()
+        .flatten()
+        .map(|item| item.accounts[1].pubkey)
+        .collect();
+    price_feeds.sort();
+    price_feeds.dedup();
+    ensure_fresh_price_feeds(
+        rpc,
+        &price_feeds,
+        max_price_age_secs,
+        pyth_receiver_program_id,
+    )?;
+
+    for ((group_id, group_asset_pda, _), items) in pending.into_iter().zip(items_by_group) {
+        debug!(group_asset_pda = %group_asset_pda, "capturing start price for group");
+        let instruction = ix::capture_start_price::instruction(
+            program_id,
+            &ix::capture_start_price::Accounts {
+                signer: signers.authority_pubkey(),
+                config: *config_pda,
+                round: *round_pda,
+                group_asset: group_asset_pda,
+                system_program: *system_program_id,
+            },
+        );
 
-            set_tx_context(TxContext {
+        let chunk_sigs = send_chunked(
+            rpc,
+            signers,
+            &instruction,
+            &items,
+            lookup_tables,
+            |start, end| TxContext {
                 keeper_type: "start".into(),
                 op: "capture_start_price_assets_chunk".into(),
                 round_id: Some(round.id as i64),
                 group_id: Some(group_id as i64),
-                range_start: Some(start_asset as i64),
-                range_end: Some(end_asset as i64),
-            });
-            let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-            clear_tx_context();
-            debug!(start_asset, end_asset, tx_sig = %sig, "captured start price assets chunk");
-            sigs.push(sig);
-
-            start_asset = end_asset.saturating_add(1);
-        }
+                range_start: Some(start as i64),
+                range_end: Some(end as i64),
+            },
+        )?;
+        debug!(
+            group_id,
+            chunks = chunk_sigs.len(),
+            "captured start price for group"
+        );
+        sigs.extend(chunk_sigs);
     }
 
     Ok(sigs)
@@ -318,11 +865,12 @@ pub fn capture_start_price(
 /// Finalize the start price for a group
 pub fn finalize_start_group_assets(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -333,18 +881,22 @@ pub fn finalize_start_group_assets(
         bail!("finalize_start_group_assets requires at least one group");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let data = sighash_global("finalize_start_group_asset").to_vec();
-
     info!(round_pda = %round_pda, "finalizing start group assets");
 
-    for group_id in 1..=round.total_groups {
-        let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id);
-        let group_asset = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
+    let started = Instant::now();
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, round.total_groups)?;
+    log_fetch_latency(
+        round.id,
+        "finalize_start_group_assets",
+        groups.len(),
+        started,
+    );
 
+    for (group_id, (group_asset_pda, group_asset)) in (1u64..).zip(groups) {
         if group_asset.total_assets < 1 {
             debug!(group_id, "group has no assets");
             continue;
@@ -360,58 +912,39 @@ pub fn finalize_start_group_assets(
             continue;
         }
 
-        let total_assets = group_asset.total_assets as usize;
-        let mut start_asset = 1usize;
-        while start_asset <= total_assets {
-            let end_asset = (start_asset + max_remaining_accounts - 1).min(total_assets);
-            debug!(
-                start_asset,
-                end_asset, max_remaining_accounts, "finalizing start group assets chunk"
-            );
-
-            let mut remaining_accounts: Vec<AccountMeta> =
-                Vec::with_capacity(end_asset - start_asset + 1);
-            for asset_id in start_asset..=end_asset {
-                let asset_pda = derive_asset_pda(program_id, &group_asset_pda, asset_id as u64);
-                remaining_accounts.push(AccountMeta {
-                    pubkey: asset_pda,
-                    is_signer: false,
-                    is_writable: true,
-                });
-            }
-
-            let accounts = vec![
-                AccountMeta::new(payer.pubkey(), true),
-                AccountMeta::new(*config_pda, false),
-                AccountMeta::new(*round_pda, false),
-                AccountMeta::new(group_asset_pda, false),
-                AccountMeta::new_readonly(*system_program_id, false),
-            ]
-            .into_iter()
-            .chain(remaining_accounts.into_iter())
-            .collect();
-
-            let instruction = Instruction {
-                data: data.clone(),
-                accounts,
-                program_id: *program_id,
-            };
-
-            set_tx_context(TxContext {
+        let instruction = ix::finalize_start_group_asset::instruction(
+            program_id,
+            &ix::finalize_start_group_asset::Accounts {
+                signer: signers.authority_pubkey(),
+                config: *config_pda,
+                round: *round_pda,
+                group_asset: group_asset_pda,
+                system_program: *system_program_id,
+            },
+        );
+        let items = asset_items(&group_asset_pda, group_asset.total_assets, program_id);
+
+        let chunk_sigs = send_chunked(
+            rpc,
+            signers,
+            &instruction,
+            &items,
+            lookup_tables,
+            |start, end| TxContext {
                 keeper_type: "start".into(),
                 op: "finalize_start_group_assets_chunk".into(),
                 round_id: Some(round.id as i64),
                 group_id: Some(group_id as i64),
-                range_start: Some(start_asset as i64),
-                range_end: Some(end_asset as i64),
-            });
-            let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-            clear_tx_context();
-            debug!(group_id, tx_sig = %sig, "finalize_start_group_assets chunk");
-            sigs.push(sig);
-
-            start_asset = end_asset.saturating_add(1);
-        }
+                range_start: Some(start as i64),
+                range_end: Some(end as i64),
+            },
+        )?;
+        debug!(
+            group_id,
+            chunks = chunk_sigs.len(),
+            "finalized start group assets"
+        );
+        sigs.extend(chunk_sigs);
     }
 
     Ok(sigs)
@@ -420,11 +953,12 @@ pub fn finalize_start_group_assets(
 /// Finalize the start groups
 pub fn finalize_start_groups(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -439,85 +973,58 @@ pub fn finalize_start_groups(
         bail!("finalize_start_groups already captured start groups");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
-
-    let mut sigs: Vec<Signature> = Vec::new();
-
-    let data = sighash_global("finalize_start_groups").to_vec();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     info!(round_pda = %round_pda, "finalizing start groups");
 
-    let base_accounts = vec![
-        AccountMeta::new(payer.pubkey(), true),
-        AccountMeta::new(*config_pda, false),
-        AccountMeta::new(*round_pda, false),
-        AccountMeta::new_readonly(*system_program_id, false),
-    ];
-
-    let total_groups = round.total_groups as usize;
-    let mut start_group = 1usize;
-    while start_group <= total_groups {
-        let end_group = (start_group + max_remaining_accounts - 1).min(total_groups);
-        debug!(
-            start_group,
-            end_group, max_remaining_accounts, "finalizing start groups chunk"
-        );
-
-        let mut remaining_accounts: Vec<AccountMeta> = Vec::new();
-
-        for group_id in start_group..=end_group {
-            let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id as u64);
-            let group = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
-            if group.total_assets == 0 {
-                debug!(group_id, "group has no assets");
-                continue;
-            }
-
-            remaining_accounts.push(AccountMeta {
-                pubkey: group_asset_pda,
-                is_signer: false,
-                is_writable: false,
-            });
-        }
-        if remaining_accounts.is_empty() {
-            debug!("no groups to finalize");
-            start_group = end_group.saturating_add(1);
-            continue;
-        }
-
-        let mut accounts = base_accounts.clone();
-        accounts.reserve(remaining_accounts.len());
-        accounts.extend(remaining_accounts.into_iter());
-
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts,
-            program_id: *program_id,
-        };
+    let instruction = ix::finalize_start_groups::instruction(
+        program_id,
+        &ix::finalize_start_groups::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            system_program: *system_program_id,
+        },
+    );
+    let started = Instant::now();
+    let items = group_items(rpc, round_pda, round.total_groups, program_id)?;
+    log_fetch_latency(
+        round.id,
+        "finalize_start_groups",
+        round.total_groups as usize,
+        started,
+    );
+    if items.is_empty() {
+        debug!("no groups to finalize");
+        return Ok(Vec::new());
+    }
 
-        set_tx_context(TxContext {
+    let sigs = send_chunked(
+        rpc,
+        signers,
+        &instruction,
+        &items,
+        lookup_tables,
+        |start, end| TxContext {
             keeper_type: "start".into(),
             op: "finalize_start_groups_chunk".into(),
             round_id: Some(round.id as i64),
             group_id: None,
-            range_start: Some(start_group as i64),
-            range_end: Some(end_group as i64),
-        });
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-        clear_tx_context();
-        debug!(start_group, end_group, tx_sig = %sig, "finalized start groups chunk");
-        sigs.push(sig);
-
-        start_group = end_group.saturating_add(1);
-    }
+            range_start: Some(start as i64),
+            range_end: Some(end as i64),
+        },
+    )?;
+    debug!(chunks = sigs.len(), "finalized start groups");
 
     Ok(sigs)
 }
 
 /// Settle the single round
+///
+/// Nothing is sent while the gold price feed is stale (`StalePriceError`).
 pub fn settle_single_round(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round_vault: &Pubkey,
@@ -525,39 +1032,51 @@ pub fn settle_single_round(
     treasury: &Pubkey,
     treasury_token_account: &Pubkey,
     gold_price_feed: &Pubkey,
+    max_price_age_secs: u64,
+    pyth_receiver_program_id: &Pubkey,
     token_mint: &Pubkey,
     token_program_id: &Pubkey,
     associated_token_program_id: &Pubkey,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Signature> {
-    let max_remaining_accounts = rpc.max_remaining_accounts();
-
-    let data = sighash_global("settle_single_round").to_vec();
-
-    let base_accounts = || -> Vec<AccountMeta> {
-        vec![
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new(*config_pda, false),
-            AccountMeta::new(*round_pda, false),
-            AccountMeta::new(*round_vault, false),
-            AccountMeta::new_readonly(*gold_price_feed, false),
-            AccountMeta::new_readonly(*treasury, false),
-            AccountMeta::new(*treasury_token_account, false),
-            AccountMeta::new_readonly(*token_mint, false),
-            AccountMeta::new_readonly(*token_program_id, false),
-            AccountMeta::new_readonly(*associated_token_program_id, false),
-            AccountMeta::new_readonly(*system_program_id, false),
-        ]
-    };
-
-    // If there are no bets, settle the round immediately
-    if round.total_bets == 0 {
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts: base_accounts(),
-            program_id: *program_id,
-        };
+    ensure_fresh_price_feeds(
+        rpc,
+        slice::from_ref(gold_price_feed),
+        max_price_age_secs,
+        pyth_receiver_program_id,
+    )?;
+
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
+
+    let instruction = ix::settle_single_round::instruction(
+        program_id,
+        &ix::settle_single_round::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            round_vault: *round_vault,
+            price_update: *gold_price_feed,
+            treasury: *treasury,
+            treasury_token_account: *treasury_token_account,
+            mint: *token_mint,
+            token_program: *token_program_id,
+            associated_token_program: *associated_token_program_id,
+            system_program: *system_program_id,
+        },
+    );
+
+    // If there are no open bets, settle the round immediately
+    let started = Instant::now();
+    let items = bet_items(rpc, round_pda, round.total_bets, program_id)?;
+    log_fetch_latency(
+        round.id,
+        "settle_single_round",
+        round.total_bets as usize,
+        started,
+    );
+    if items.is_empty() {
         set_tx_context(TxContext {
             keeper_type: "settle".into(),
             op: "settle_single_bets_chunk".into(),
@@ -566,60 +1085,47 @@ pub fn settle_single_round(
             range_start: None,
             range_end: None,
         });
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
+        let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
         clear_tx_context();
-        info!(round_id = %round.id, "settled single round (no bets)");
+        let sig = sig?;
+        info!(round_id = %round.id, "settled single round (no open bets)");
         return Ok(sig);
     }
 
-    let total = round.total_bets as usize;
-    let mut last_sig: Option<Signature> = None;
-
-    let mut start = 1usize;
-    while start <= total {
-        let end = (start + max_remaining_accounts - 1).min(total);
-        debug!(start, end, "settling bets chunk");
-
-        let mut remaining_accounts: Vec<AccountMeta> = Vec::with_capacity(end - start + 1);
-        for bet_id in start..=end {
-            let bet_pda = derive_bet_pda(program_id, round_pda, bet_id as u64);
-            remaining_accounts.push(AccountMeta {
-                pubkey: bet_pda,
-                is_signer: false,
-                is_writable: true,
-            });
-        }
-
-        let accounts = base_accounts()
-            .into_iter()
-            .chain(remaining_accounts.into_iter())
-            .collect();
-
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts,
-            program_id: *program_id,
-        };
-
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-        last_sig = Some(sig);
-        debug!(start, end, tx_sig = %sig, "settled bets chunk");
-
-        start = end.saturating_add(1);
-    }
+    let sigs = send_chunked(
+        rpc,
+        signers,
+        &instruction,
+        &items,
+        lookup_tables,
+        |start, end| TxContext {
+            keeper_type: "settle".into(),
+            op: "settle_single_bets_chunk".into(),
+            round_id: Some(round.id as i64),
+            group_id: None,
+            range_start: Some(start as i64),
+            range_end: Some(end as i64),
+        },
+    )?;
+    debug!(round_id = round.id, chunks = sigs.len(), "settled bets");
 
-    Ok(last_sig.expect("no signatures returned"))
+    Ok(*sigs.last().expect("no signatures returned"))
 }
 
 /// Capture the end price for a group
+///
+/// Nothing is sent while any of the price feeds is stale (`StalePriceError`).
 pub fn capture_end_price(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
+    max_price_age_secs: u64,
     push_oracle_program_id: &Pubkey,
+    pyth_receiver_program_id: &Pubkey,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -630,18 +1136,16 @@ pub fn capture_end_price(
         bail!("capture_end_price requires at least one group");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let data = sighash_global("capture_end_price").to_vec();
-
     info!(round_pda = %round_pda, "capturing end price");
 
-    for group_id in 1..=round.total_groups {
-        let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id);
-        debug!(group_asset_pda = %group_asset_pda, "capturing end price for group");
-        let group_asset = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
+    let started = Instant::now();
+    let mut pending = Vec::new();
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, round.total_groups)?;
+    for (group_id, (group_asset_pda, group_asset)) in (1u64..).zip(groups) {
         if group_asset.total_assets < 1 {
             debug!(group_id, "group has no assets");
             continue;
@@ -651,70 +1155,69 @@ pub fn capture_end_price(
             continue;
         }
 
-        let base_accounts: Vec<AccountMeta> = vec![
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new(*config_pda, false),
-            AccountMeta::new(*round_pda, false),
-            AccountMeta::new(group_asset_pda, false),
-            AccountMeta::new_readonly(*system_program_id, false),
-        ];
-
-        let per_asset_accounts = 2;
-        let max_assets_per_batch = (max_remaining_accounts / per_asset_accounts).max(1);
-        let total_assets = group_asset.total_assets as usize;
-        let mut start_asset = 1usize;
-        while start_asset <= total_assets {
-            let end_asset = (start_asset + max_assets_per_batch - 1).min(total_assets);
-            debug!(
-                start_asset,
-                end_asset, max_assets_per_batch, "capturing end price assets chunk"
-            );
-
-            let mut remaining_accounts: Vec<AccountMeta> =
-                Vec::with_capacity((end_asset - start_asset + 1) * per_asset_accounts);
-            for asset_id in start_asset..=end_asset {
-                let asset_pda = derive_asset_pda(program_id, &group_asset_pda, asset_id as u64);
-                remaining_accounts.push(AccountMeta {
-                    pubkey: asset_pda,
-                    is_signer: false,
-                    is_writable: true,
-                });
-
-                let asset = get_asset_account(rpc.client(), &asset_pda, program_id)?;
-                let price_feed_account =
-                    get_price_feed_account(0, &hex::encode(asset.feed_id), push_oracle_program_id)?;
-                remaining_accounts.push(AccountMeta {
-                    pubkey: price_feed_account,
-                    is_signer: false,
-                    is_writable: false,
-                });
-            }
-
-            let mut accounts = base_accounts.clone();
-            accounts.reserve(remaining_accounts.len());
-            accounts.extend(remaining_accounts.into_iter());
-
-            let instruction = Instruction {
-                data: data.clone(),
-                accounts,
-                program_id: *program_id,
-            };
+        pending.push((group_id, group_asset_pda, group_asset.total_assets));
+    }
+    let capture_groups: Vec<(Pubkey, u64)> = pending
+        .iter()
+        .map(|(_, group_asset_pda, total_assets)| (*group_asset_pda, *total_assets))
+        .collect();
+    let items_by_group =
+        asset_price_items(rpc, &capture_groups, push_oracle_program_id, program_id)?;
+    let accounts = round.total_groups as usize
+        + capture_groups
+            .iter()
+            .map(|(_, n)| *n as usize)
+            .sum::<usize>();
+    log_fetch_latency(round.id, "capture_end_price", accounts, started);
+
+    let mut price_feeds: Vec<Pubkey> = items_by_group
+        .iter()
+        .flatten()
+        .map(|item| item.accounts[1].pubkey)
+        .collect();
+    price_feeds.sort();
+    price_feeds.dedup();
+    ensure_fresh_price_feeds(
+        rpc,
+        &price_feeds,
+        max_price_age_secs,
+        pyth_receiver_program_id,
+    )?;
+
+    for ((group_id, group_asset_pda, _), items) in pending.into_iter().zip(items_by_group) {
+        debug!(group_asset_pda = %group_asset_pda, "capturing end price for group");
+        let instruction = ix::capture_end_price::instruction(
+            program_id,
+            &ix::capture_end_price::Accounts {
+                signer: signers.authority_pubkey(),
+                config: *config_pda,
+                round: *round_pda,
+                group_asset: group_asset_pda,
+                system_program: *system_program_id,
+            },
+        );
 
-            set_tx_context(TxContext {
+        let chunk_sigs = send_chunked(
+            rpc,
+            signers,
+            &instruction,
+            &items,
+            lookup_tables,
+            |start, end| TxContext {
                 keeper_type: "settle".into(),
                 op: "capture_end_price_assets_chunk".into(),
                 round_id: Some(round.id as i64),
                 group_id: Some(group_id as i64),
-                range_start: Some(start_asset as i64),
-                range_end: Some(end_asset as i64),
-            });
-            let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-            clear_tx_context();
-            debug!(start_asset, end_asset, tx_sig = %sig, "captured end price assets chunk");
-            sigs.push(sig);
-
-            start_asset = end_asset.saturating_add(1);
-        }
+                range_start: Some(start as i64),
+                range_end: Some(end as i64),
+            },
+        )?;
+        debug!(
+            group_id,
+            chunks = chunk_sigs.len(),
+            "captured end price for group"
+        );
+        sigs.extend(chunk_sigs);
     }
 
     Ok(sigs)
@@ -723,11 +1226,12 @@ pub fn capture_end_price(
 /// Finalize the end group assets
 pub fn finalize_end_group_assets(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -738,18 +1242,17 @@ pub fn finalize_end_group_assets(
         bail!("finalize_end_group_assets requires at least one group");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let data = sighash_global("finalize_end_group_asset").to_vec();
-
     info!(round_pda = %round_pda, "finalizing end group assets");
 
-    for group_id in 1..=round.total_groups {
-        let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id);
-        let group_asset = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
+    let started = Instant::now();
+    let groups = get_group_asset_accounts(rpc, program_id, round_pda, round.total_groups)?;
+    log_fetch_latency(round.id, "finalize_end_group_assets", groups.len(), started);
 
+    for (group_id, (group_asset_pda, group_asset)) in (1u64..).zip(groups) {
         if group_asset.total_assets < 1 {
             debug!(group_id, "group has no assets");
             continue;
@@ -765,58 +1268,39 @@ pub fn finalize_end_group_assets(
             continue;
         }
 
-        let total_assets = group_asset.total_assets as usize;
-        let mut start_asset = 1usize;
-        while start_asset <= total_assets {
-            let end_asset = (start_asset + max_remaining_accounts - 1).min(total_assets);
-            debug!(
-                start_asset,
-                end_asset, max_remaining_accounts, "finalizing end group assets chunk"
-            );
-
-            let mut remaining_accounts: Vec<AccountMeta> =
-                Vec::with_capacity(end_asset - start_asset + 1);
-            for asset_id in start_asset..=end_asset {
-                let asset_pda = derive_asset_pda(program_id, &group_asset_pda, asset_id as u64);
-                remaining_accounts.push(AccountMeta {
-                    pubkey: asset_pda,
-                    is_signer: false,
-                    is_writable: true,
-                });
-            }
-
-            let accounts = vec![
-                AccountMeta::new(payer.pubkey(), true),
-                AccountMeta::new(*config_pda, false),
-                AccountMeta::new(*round_pda, false),
-                AccountMeta::new(group_asset_pda, false),
-                AccountMeta::new_readonly(*system_program_id, false),
-            ]
-            .into_iter()
-            .chain(remaining_accounts.into_iter())
-            .collect();
-
-            let instruction = Instruction {
-                data: data.clone(),
-                accounts,
-                program_id: *program_id,
-            };
-
-            set_tx_context(TxContext {
+        let instruction = ix::finalize_end_group_asset::instruction(
+            program_id,
+            &ix::finalize_end_group_asset::Accounts {
+                signer: signers.authority_pubkey(),
+                config: *config_pda,
+                round: *round_pda,
+                group_asset: group_asset_pda,
+                system_program: *system_program_id,
+            },
+        );
+        let items = asset_items(&group_asset_pda, group_asset.total_assets, program_id);
+
+        let chunk_sigs = send_chunked(
+            rpc,
+            signers,
+            &instruction,
+            &items,
+            lookup_tables,
+            |start, end| TxContext {
                 keeper_type: "settle".into(),
                 op: "finalize_end_group_assets_chunk".into(),
                 round_id: Some(round.id as i64),
                 group_id: Some(group_id as i64),
-                range_start: Some(start_asset as i64),
-                range_end: Some(end_asset as i64),
-            });
-            let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-            clear_tx_context();
-            debug!(start_asset, end_asset, tx_sig = %sig, "finalized end group assets chunk");
-            sigs.push(sig);
-
-            start_asset = end_asset.saturating_add(1);
-        }
+                range_start: Some(start as i64),
+                range_end: Some(end as i64),
+            },
+        )?;
+        debug!(
+            group_id,
+            chunks = chunk_sigs.len(),
+            "finalized end group assets"
+        );
+        sigs.extend(chunk_sigs);
     }
 
     Ok(sigs)
@@ -825,11 +1309,12 @@ pub fn finalize_end_group_assets(
 /// Finalize the end groups
 pub fn finalize_end_groups(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
     if !matches!(round.market_type, MarketType::GroupBattle) {
@@ -844,77 +1329,48 @@ pub fn finalize_end_groups(
         bail!("finalize_end_groups already captured end groups");
     }
 
-    let max_remaining_accounts = rpc.max_remaining_accounts();
-
-    let mut sigs: Vec<Signature> = Vec::new();
-
-    let data = sighash_global("finalize_end_groups").to_vec();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     info!(round_pda = %round_pda, "finalizing end groups");
 
-    let base_accounts = vec![
-        AccountMeta::new(payer.pubkey(), true),
-        AccountMeta::new(*config_pda, false),
-        AccountMeta::new(*round_pda, false),
-        AccountMeta::new_readonly(*system_program_id, false),
-    ];
-
-    let total_groups = round.total_groups as usize;
-    let mut start_group = 1usize;
-    while start_group <= total_groups {
-        let end_group = (start_group + max_remaining_accounts - 1).min(total_groups);
-        debug!(
-            start_group,
-            end_group, max_remaining_accounts, "finalizing end groups chunk"
-        );
-
-        let mut remaining_accounts: Vec<AccountMeta> = Vec::new();
-
-        for group_id in start_group..=end_group {
-            let group_asset_pda = derive_group_asset_pda(program_id, round_pda, group_id as u64);
-            let group = get_group_asset_account(rpc.client(), program_id, &group_asset_pda)?;
-            if group.total_assets == 0 {
-                debug!(group_id, "group has no assets");
-                continue;
-            }
-
-            remaining_accounts.push(AccountMeta {
-                pubkey: group_asset_pda,
-                is_signer: false,
-                is_writable: false,
-            });
-        }
-        if remaining_accounts.is_empty() {
-            debug!("no groups to finalize");
-            start_group = end_group.saturating_add(1);
-            continue;
-        }
-
-        let mut accounts = base_accounts.clone();
-        accounts.reserve(remaining_accounts.len());
-        accounts.extend(remaining_accounts.into_iter());
-
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts,
-            program_id: *program_id,
-        };
+    let instruction = ix::finalize_end_groups::instruction(
+        program_id,
+        &ix::finalize_end_groups::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            system_program: *system_program_id,
+        },
+    );
+    let started = Instant::now();
+    let items = group_items(rpc, round_pda, round.total_groups, program_id)?;
+    log_fetch_latency(
+        round.id,
+        "finalize_end_groups",
+        round.total_groups as usize,
+        started,
+    );
+    if items.is_empty() {
+        debug!("no groups to finalize");
+        return Ok(Vec::new());
+    }
 
-        set_tx_context(TxContext {
+    let sigs = send_chunked(
+        rpc,
+        signers,
+        &instruction,
+        &items,
+        lookup_tables,
+        |start, end| TxContext {
             keeper_type: "settle".into(),
             op: "finalize_end_groups_chunk".into(),
             round_id: Some(round.id as i64),
             group_id: None,
-            range_start: Some(start_group as i64),
-            range_end: Some(end_group as i64),
-        });
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-        clear_tx_context();
-        debug!(start_group, end_group, tx_sig = %sig, "finalized end groups chunk");
-        sigs.push(sig);
-
-        start_group = end_group.saturating_add(1);
-    }
+            range_start: Some(start as i64),
+            range_end: Some(end as i64),
+        },
+    )?;
+    debug!(chunks = sigs.len(), "finalized end groups");
 
     Ok(sigs)
 }
@@ -922,7 +1378,7 @@ pub fn finalize_end_groups(
 /// Settle the group round
 pub fn settle_group_round(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round_vault: &Pubkey,
@@ -933,87 +1389,377 @@ pub fn settle_group_round(
     token_program_id: &Pubkey,
     associated_token_program_id: &Pubkey,
     system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
     program_id: &Pubkey,
 ) -> Result<Vec<Signature>> {
-    let max_remaining_accounts = rpc.max_remaining_accounts();
-
-    let data = sighash_global("settle_group_round").to_vec();
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
 
     info!(round_pda = %round_pda, "settling group round");
 
-    let base_accounts = || -> Vec<AccountMeta> {
-        vec![
-            AccountMeta::new(payer.pubkey(), true),
-            AccountMeta::new(*config_pda, false),
-            AccountMeta::new(*round_pda, false),
-            AccountMeta::new(*round_vault, false),
-            AccountMeta::new_readonly(*treasury, false),
-            AccountMeta::new(*treasury_token_account, false),
-            AccountMeta::new_readonly(*token_mint, false),
-            AccountMeta::new_readonly(*token_program_id, false),
-            AccountMeta::new_readonly(*associated_token_program_id, false),
-            AccountMeta::new_readonly(*system_program_id, false),
-        ]
-    };
+    let instruction = ix::settle_group_round::instruction(
+        program_id,
+        &ix::settle_group_round::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            round_vault: *round_vault,
+            treasury: *treasury,
+            treasury_token_account: *treasury_token_account,
+            mint: *token_mint,
+            token_program: *token_program_id,
+            associated_token_program: *associated_token_program_id,
+            system_program: *system_program_id,
+        },
+    );
+
+    // If there are no open bets, settle the round immediately
+    let started = Instant::now();
+    let items = bet_items(rpc, round_pda, round.total_bets, program_id)?;
+    log_fetch_latency(
+        round.id,
+        "settle_group_round",
+        round.total_bets as usize,
+        started,
+    );
+    if items.is_empty() {
+        set_tx_context(TxContext {
+            keeper_type: "settle".into(),
+            op: "settle_group_bets_chunk".into(),
+            round_id: Some(round.id as i64),
+            group_id: None,
+            range_start: None,
+            range_end: None,
+        });
+        let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
+        clear_tx_context();
+        let sig = sig?;
+        info!(round_id = %round.id, "settled group round (no open bets)");
 
-    let mut sigs: Vec<Signature> = Vec::new();
+        return Ok(vec![sig]);
+    }
 
-    // If there are no bets, settle the round immediately
-    if round.total_bets == 0 {
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts: base_accounts(),
-            program_id: *program_id,
-        };
-        set_tx_context(TxContext {
+    let sigs = send_chunked(
+        rpc,
+        signers,
+        &instruction,
+        &items,
+        lookup_tables,
+        |start, end| TxContext {
             keeper_type: "settle".into(),
             op: "settle_group_bets_chunk".into(),
             round_id: Some(round.id as i64),
             group_id: None,
+            range_start: Some(start as i64),
+            range_end: Some(end as i64),
+        },
+    )?;
+    debug!(round_id = round.id, chunks = sigs.len(), "settled bets");
+
+    Ok(sigs)
+}
+
+/// Cancel a round, refunding its open bets to the round vault for withdrawal
+pub fn cancel_round(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    config_pda: &Pubkey,
+    round_pda: &Pubkey,
+    round_vault: &Pubkey,
+    round: &RoundAccount,
+    treasury: &Pubkey,
+    token_program_id: &Pubkey,
+    system_program_id: &Pubkey,
+    lookup_table: Option<&AddressLookupTableAccount>,
+    program_id: &Pubkey,
+) -> Result<Vec<Signature>> {
+    let lookup_tables = lookup_table.map(slice::from_ref).unwrap_or_default();
+
+    info!(round_pda = %round_pda, status = ?round.status, "cancelling round");
+
+    let instruction = ix::cancel_round::instruction(
+        program_id,
+        &ix::cancel_round::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: *round_pda,
+            round_vault: *round_vault,
+            treasury: *treasury,
+            token_program: *token_program_id,
+            system_program: *system_program_id,
+        },
+    );
+
+    // If there are no open bets, cancel the round immediately
+    let started = Instant::now();
+    let items = bet_items(rpc, round_pda, round.total_bets, program_id)?;
+    log_fetch_latency(round.id, "cancel_round", round.total_bets as usize, started);
+    if items.is_empty() {
+        set_tx_context(TxContext {
+            keeper_type: "cancel".into(),
+            op: "cancel_round".into(),
+            round_id: Some(round.id as i64),
+            group_id: None,
             range_start: None,
             range_end: None,
         });
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
+        let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
         clear_tx_context();
-        info!(round_id = %round.id, "settled group round (no bets)");
-        sigs.push(sig);
+        let sig = sig?;
+        info!(round_id = %round.id, "cancelled round (no open bets)");
 
-        return Ok(sigs);
+        return Ok(vec![sig]);
     }
 
-    let total = round.total_bets as usize;
-    let mut start = 1usize;
-    while start <= total {
-        let end = (start + max_remaining_accounts - 1).min(total);
-        debug!(start, end, "settling bets chunk");
+    let sigs = send_chunked(
+        rpc,
+        signers,
+        &instruction,
+        &items,
+        lookup_tables,
+        |start, end| TxContext {
+            keeper_type: "cancel".into(),
+            op: "cancel_round_bets_chunk".into(),
+            round_id: Some(round.id as i64),
+            group_id: None,
+            range_start: Some(start as i64),
+            range_end: Some(end as i64),
+        },
+    )?;
+    debug!(round_id = round.id, chunks = sigs.len(), "cancelled bets");
 
-        let mut remaining_accounts: Vec<AccountMeta> = Vec::with_capacity(end - start + 1);
-        for bet_id in start..=end {
-            let bet_pda = derive_bet_pda(program_id, round_pda, bet_id as u64);
-            remaining_accounts.push(AccountMeta {
-                pubkey: bet_pda,
-                is_signer: false,
-                is_writable: true,
-            });
-        }
+    Ok(sigs)
+}
 
-        let accounts = base_accounts()
-            .into_iter()
-            .chain(remaining_accounts.into_iter())
-            .collect();
+/// Create the round `round_id` (the config's `current_round_counter` + 1)
+pub fn create_round(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    config_pda: &Pubkey,
+    round_id: u64,
+    market_type: MarketType,
+    start_time: i64,
+    end_time: i64,
+    token_mint: &Pubkey,
+    system_program_id: &Pubkey,
+    token_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(program_id, round_id);
+    let round_vault = derive_round_vault_pda(program_id, &round_pda);
+
+    info!(round_id, market_type = ?market_type, start_time, end_time, "creating round");
+
+    let instruction = ix::create_round::instruction(
+        program_id,
+        &ix::create_round::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: round_pda,
+            vault: round_vault,
+            mint: *token_mint,
+            system_program: *system_program_id,
+            token_program: *token_program_id,
+        },
+        &ix::create_round::Args {
+            market_type,
+            start_time,
+            end_time,
+        },
+    );
+
+    set_tx_context(TxContext {
+        keeper_type: "create".into(),
+        op: "create_round".into(),
+        round_id: Some(round_id as i64),
+        group_id: None,
+        range_start: None,
+        range_end: None,
+    });
+    let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
+    clear_tx_context();
+
+    sig
+}
 
-        let instruction = Instruction {
-            data: data.clone(),
-            accounts,
-            program_id: *program_id,
-        };
+/// Add group `group_id` (the round's `total_groups` + 1) to a group battle round
+pub fn insert_group_asset(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    config_pda: &Pubkey,
+    round_id: u64,
+    group_id: u64,
+    symbol: [u8; 8],
+    system_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(program_id, round_id);
+    let group_asset_pda = derive_group_asset_pda(program_id, &round_pda, group_id);
+
+    let instruction = ix::insert_group_asset::instruction(
+        program_id,
+        &ix::insert_group_asset::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: round_pda,
+            group_asset: group_asset_pda,
+            system_program: *system_program_id,
+        },
+        &ix::insert_group_asset::Args { symbol },
+    );
+
+    set_tx_context(TxContext {
+        keeper_type: "create".into(),
+        op: "insert_group_asset".into(),
+        round_id: Some(round_id as i64),
+        group_id: Some(group_id as i64),
+        range_start: None,
+        range_end: None,
+    });
+    let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
+    clear_tx_context();
+
+    sig
+}
 
-        let sig = send_tx_with_retry(&rpc, payer, [instruction].to_vec())?;
-        sigs.push(sig);
-        debug!(start, end, tx_sig = %sig, "settled bets chunk");
+/// Add asset `asset_id` (the group's `total_assets` + 1) to a group of a group battle round
+pub fn insert_asset(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    config_pda: &Pubkey,
+    round_id: u64,
+    group_id: u64,
+    asset_id: u64,
+    symbol: [u8; 8],
+    price_feed_account: &Pubkey,
+    system_program_id: &Pubkey,
+    program_id: &Pubkey,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(program_id, round_id);
+    let group_asset_pda = derive_group_asset_pda(program_id, &round_pda, group_id);
+    let asset_pda = derive_asset_pda(program_id, &group_asset_pda, asset_id);
+
+    let instruction = ix::insert_asset::instruction(
+        program_id,
+        &ix::insert_asset::Accounts {
+            signer: signers.authority_pubkey(),
+            config: *config_pda,
+            round: round_pda,
+            group_asset: group_asset_pda,
+            asset: asset_pda,
+            feed_price_account: *price_feed_account,
+            system_program: *system_program_id,
+        },
+        &ix::insert_asset::Args { symbol },
+    );
+
+    set_tx_context(TxContext {
+        keeper_type: "create".into(),
+        op: "insert_asset".into(),
+        round_id: Some(round_id as i64),
+        group_id: Some(group_id as i64),
+        range_start: Some(asset_id as i64),
+        range_end: Some(asset_id as i64),
+    });
+    let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
+    clear_tx_context();
+
+    sig
+}
 
-        start = end.saturating_add(1);
-    }
+/// `programPause`, signed by the config `admin`
+pub fn program_pause_instruction(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix::program_pause::instruction(
+        program_id,
+        &ix::program_pause::Accounts {
+            signer: *admin,
+            config: *config_pda,
+        },
+    )
+}
 
-    Ok(sigs)
+/// `programUnpause`, signed by the config `admin`
+pub fn program_unpause_instruction(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix::program_unpause::instruction(
+        program_id,
+        &ix::program_unpause::Accounts {
+            signer: *admin,
+            config: *config_pda,
+        },
+    )
+}
+
+/// `emergencyPause`, signed by the config `admin`
+pub fn emergency_pause_instruction(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix::emergency_pause::instruction(
+        program_id,
+        &ix::emergency_pause::Accounts {
+            signer: *admin,
+            config: *config_pda,
+        },
+    )
+}
+
+/// `emergencyUnpause`, signed by the config `admin`
+pub fn emergency_unpause_instruction(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix::emergency_unpause::instruction(
+        program_id,
+        &ix::emergency_unpause::Accounts {
+            signer: *admin,
+            config: *config_pda,
+        },
+    )
+}
+
+/// `updateConfig` of the fields set in `args`, signed by the config `admin`
+pub fn update_config_instruction(
+    admin: &Pubkey,
+    config_pda: &Pubkey,
+    args: &ix::update_config::Args,
+    program_id: &Pubkey,
+) -> Instruction {
+    ix::update_config::instruction(
+        program_id,
+        &ix::update_config::Accounts {
+            signer: *admin,
+            config: *config_pda,
+        },
+        args,
+    )
+}
+
+/// Send an admin instruction signed by the keeper authority (which must be the admin)
+pub fn send_admin_tx(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    op: &str,
+    instruction: Instruction,
+) -> Result<Signature> {
+    set_tx_context(TxContext {
+        keeper_type: "admin".into(),
+        op: op.into(),
+        round_id: None,
+        group_id: None,
+        range_start: None,
+        range_end: None,
+    });
+    let sig = send_tx_with_retry(rpc, signers, [instruction].to_vec());
+    clear_tx_context();
+
+    sig
 }
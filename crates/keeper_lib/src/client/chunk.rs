@@ -0,0 +1,309 @@
+use anyhow::{Context, Result};
+use solana_compute_budget_interface::ComputeBudgetInstruction;
+use solana_sdk::{
+    hash::Hash,
+    instruction::{AccountMeta, Instruction},
+    message::{AddressLookupTableAccount, Message, VersionedMessage, v0},
+    pubkey::Pubkey,
+    signature::Signature,
+};
+use tracing::{debug, warn};
+
+use crate::client::rpc::{Rpc, send_v0_tx_with_retry};
+use crate::errors::TxTooLargeError;
+use crate::storage::sqlite::{TxContext, clear_tx_context, set_tx_context};
+use crate::wallet::TxSigners;
+
+/// Max serialized transaction size (IPv6 MTU minus headers)
+pub const PACKET_DATA_SIZE: usize = 1232;
+/// Max accounts a transaction may lock
+pub const MAX_TX_ACCOUNTS: usize = 64;
+/// Size of one signature in the serialized transaction
+const SIGNATURE_SIZE: usize = 64;
+
+/// Remaining accounts that have to go in the same chunk (e.g. an asset and its price feed)
+#[derive(Clone, Debug)]
+pub struct ChunkItem {
+    /// Id reported as the chunk range (asset, group or bet id)
+    pub id: u64,
+    pub accounts: Vec<AccountMeta>,
+}
+
+/// Packs remaining accounts into as few transactions as fit
+///
+/// A chunk is bounded by the serialized tx size, the account lock limit and the operator's
+/// `max_remaining_accounts`. The bound shrinks when a chunk fails for being too large or
+/// running out of compute units, and stays shrunk for the rest of the operation.
+pub struct ChunkPlanner<'a> {
+    payer: Pubkey,
+    lookup_tables: &'a [AddressLookupTableAccount],
+    max_remaining_accounts: usize,
+    max_items: usize,
+}
+
+impl<'a> ChunkPlanner<'a> {
+    pub fn new(
+        payer: Pubkey,
+        lookup_tables: &'a [AddressLookupTableAccount],
+        max_remaining_accounts: usize,
+    ) -> Self {
+        Self {
+            payer,
+            lookup_tables,
+            max_remaining_accounts,
+            max_items: usize::MAX,
+        }
+    }
+
+    /// Number of leading items that fit in one transaction with the base instruction (at least 1)
+    pub fn plan(&self, base_ix: &Instruction, items: &[ChunkItem]) -> Result<usize> {
+        let mut hi = items.len().min(self.max_items);
+        // Operator ceiling on remaining accounts
+        let mut accounts = 0;
+        for (i, item) in items.iter().take(hi).enumerate() {
+            accounts += item.accounts.len();
+            if accounts > self.max_remaining_accounts {
+                hi = i;
+                break;
+            }
+        }
+        if hi <= 1 {
+            return Ok(1);
+        }
+
+        // Fitting is monotonic in the number of items, so binary search the largest
+        let mut lo = 1;
+        while lo < hi {
+            let mid = (lo + hi).div_ceil(2);
+            if self.fits(base_ix, &items[..mid])? {
+                lo = mid;
+            } else {
+                hi = mid - 1;
+            }
+        }
+
+        Ok(lo)
+    }
+
+    /// Lower the bound after a chunk of `tried` items failed; false if it can't go lower
+    pub fn shrink(&mut self, tried: usize) -> bool {
+        if tried <= 1 {
+            return false;
+        }
+        self.max_items = tried / 2;
+        true
+    }
+
+    /// Whether the base instruction with the items fits in one transaction
+    fn fits(&self, base_ix: &Instruction, items: &[ChunkItem]) -> Result<bool> {
+        let ix = with_items(base_ix, items);
+        let (size, accounts) = self.measure(&ix)?;
+        Ok(size <= PACKET_DATA_SIZE && accounts <= MAX_TX_ACCOUNTS)
+    }
+
+    /// Serialized size and account count of the tx `send_v0_tx_with_retry` would send
+    fn measure(&self, ix: &Instruction) -> Result<(usize, usize)> {
+        // Compute budget ixs are prepended on send; the values don't change their size
+        let ixs = [
+            ComputeBudgetInstruction::set_compute_unit_price(0),
+            ComputeBudgetInstruction::set_compute_unit_limit(0),
+            ix.clone(),
+        ];
+
+        let (message, accounts) = if self.lookup_tables.is_empty() {
+            let message = Message::new_with_blockhash(&ixs, Some(&self.payer), &Hash::default());
+            let accounts = message.account_keys.len();
+            (VersionedMessage::Legacy(message), accounts)
+        } else {
+            let message =
+                v0::Message::try_compile(&self.payer, &ixs, self.lookup_tables, Hash::default())
+                    .context("Failed to compile v0 message")?;
+            let looked_up: usize = message
+                .address_table_lookups
+                .iter()
+                .map(|l| l.writable_indexes.len() + l.readonly_indexes.len())
+                .sum();
+            let accounts = message.account_keys.len() + looked_up;
+            (VersionedMessage::V0(message), accounts)
+        };
+
+        let signatures = message.header().num_required_signatures as usize;
+        // Compact-u16 signature count (1 byte below 128) + signatures + message
+        let size = 1 + signatures * SIGNATURE_SIZE + message.serialize().len();
+        Ok((size, accounts))
+    }
+}
+
+/// Base instruction with the items' accounts appended as remaining accounts
+pub fn with_items(base_ix: &Instruction, items: &[ChunkItem]) -> Instruction {
+    let mut ix = base_ix.clone();
+    ix.accounts
+        .extend(items.iter().flat_map(|item| item.accounts.iter().cloned()));
+    ix
+}
+
+/// Whether a send failed because the chunk was too big (tx size, account locks or compute units)
+pub fn is_chunk_too_large(err: &anyhow::Error) -> bool {
+    err.is::<TxTooLargeError>()
+}
+
+/// Send the base instruction over the items in planned chunks
+///
+/// `context` builds the tx context of a chunk from the first and last item ids.
+pub fn send_chunked<F>(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    base_ix: &Instruction,
+    items: &[ChunkItem],
+    lookup_tables: &[AddressLookupTableAccount],
+    context: F,
+) -> Result<Vec<Signature>>
+where
+    F: Fn(u64, u64) -> TxContext,
+{
+    let lookup_table = lookup_tables.first();
+    let mut planner = ChunkPlanner::new(
+        signers.fee_payer_pubkey(),
+        lookup_tables,
+        rpc.max_remaining_accounts_for(lookup_table),
+    );
+
+    let mut sigs = Vec::new();
+    let mut start = 0usize;
+    while start < items.len() {
+        let n = planner.plan(base_ix, &items[start..])?;
+        let chunk = &items[start..start + n];
+        let (first, last) = (chunk[0].id, chunk[n - 1].id);
+        debug!(first, last, items = n, "sending chunk");
+
+        set_tx_context(context(first, last));
+        let res = send_v0_tx_with_retry(
+            rpc,
+            signers,
+            vec![with_items(base_ix, chunk)],
+            lookup_tables,
+        );
+        clear_tx_context();
+
+        match res {
+            Ok(sig) => {
+                debug!(first, last, tx_sig = %sig, "sent chunk");
+                sigs.push(sig);
+                start += n;
+            }
+            Err(err) if is_chunk_too_large(&err) && planner.shrink(n) => {
+                warn!(first, last, items = n, error = %err, "chunk too large, shrinking");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    Ok(sigs)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn base_ix(payer: Pubkey) -> Instruction {
+        Instruction::new_with_bytes(
+            Pubkey::new_unique(),
+            &[0u8; 8],
+            vec![
+                AccountMeta::new(payer, true),
+                AccountMeta::new(Pubkey::new_unique(), false),
+                AccountMeta::new_readonly(Pubkey::new_unique(), false),
+            ],
+        )
+    }
+
+    fn items(n: u64, accounts_per_item: usize) -> Vec<ChunkItem> {
+        (1..=n)
+            .map(|id| ChunkItem {
+                id,
+                accounts: (0..accounts_per_item)
+                    .map(|_| AccountMeta::new(Pubkey::new_unique(), false))
+                    .collect(),
+            })
+            .collect()
+    }
+
+    /// Largest leading item count that fits, by trying every count
+    fn largest_fitting(planner: &ChunkPlanner, ix: &Instruction, items: &[ChunkItem]) -> usize {
+        (1..=items.len())
+            .take_while(|&n| planner.fits(ix, &items[..n]).unwrap())
+            .last()
+            .unwrap_or(1)
+    }
+
+    #[test]
+    fn plan_fills_the_packet_size() {
+        let payer = Pubkey::new_unique();
+        let ix = base_ix(payer);
+        let items = items(100, 1);
+        let planner = ChunkPlanner::new(payer, &[], usize::MAX);
+
+        let n = planner.plan(&ix, &items).unwrap();
+        assert_eq!(n, largest_fitting(&planner, &ix, &items));
+        let (size, accounts) = planner.measure(&with_items(&ix, &items[..n])).unwrap();
+        assert!(size <= PACKET_DATA_SIZE);
+        assert!(accounts <= MAX_TX_ACCOUNTS);
+        let (size, _) = planner.measure(&with_items(&ix, &items[..n + 1])).unwrap();
+        assert!(size > PACKET_DATA_SIZE);
+    }
+
+    #[test]
+    fn plan_respects_the_account_lock_limit() {
+        let payer = Pubkey::new_unique();
+        let ix = base_ix(payer);
+        let items = items(100, 1);
+        let table = AddressLookupTableAccount {
+            key: Pubkey::new_unique(),
+            addresses: items.iter().map(|item| item.accounts[0].pubkey).collect(),
+        };
+        let tables = [table];
+        let planner = ChunkPlanner::new(payer, &tables, usize::MAX);
+
+        let n = planner.plan(&ix, &items).unwrap();
+        assert_eq!(n, largest_fitting(&planner, &ix, &items));
+        let (size, accounts) = planner.measure(&with_items(&ix, &items[..n])).unwrap();
+        assert!(size <= PACKET_DATA_SIZE);
+        assert_eq!(accounts, MAX_TX_ACCOUNTS);
+    }
+
+    #[test]
+    fn plan_respects_max_remaining_accounts() {
+        let payer = Pubkey::new_unique();
+        let planner = ChunkPlanner::new(payer, &[], 5);
+
+        assert_eq!(planner.plan(&base_ix(payer), &items(10, 2)).unwrap(), 2);
+        assert_eq!(planner.plan(&base_ix(payer), &items(10, 1)).unwrap(), 5);
+    }
+
+    #[test]
+    fn plan_takes_at_least_one_item() {
+        let payer = Pubkey::new_unique();
+        let ix = base_ix(payer);
+
+        let planner = ChunkPlanner::new(payer, &[], 10);
+        assert_eq!(planner.plan(&ix, &items(3, 40)).unwrap(), 1);
+        let planner = ChunkPlanner::new(payer, &[], usize::MAX);
+        assert_eq!(planner.plan(&ix, &items(3, 40)).unwrap(), 1);
+        assert_eq!(planner.plan(&ix, &items(1, 1)).unwrap(), 1);
+    }
+
+    #[test]
+    fn shrink_halves_the_bound_down_to_one() {
+        let payer = Pubkey::new_unique();
+        let ix = base_ix(payer);
+        let items = items(100, 1);
+        let mut planner = ChunkPlanner::new(payer, &[], usize::MAX);
+
+        let n = planner.plan(&ix, &items).unwrap();
+        assert!(planner.shrink(n));
+        assert_eq!(planner.plan(&ix, &items).unwrap(), n / 2);
+        assert!(planner.shrink(2));
+        assert_eq!(planner.plan(&ix, &items).unwrap(), 1);
+        assert!(!planner.shrink(1));
+    }
+}
@@ -0,0 +1,166 @@
+//! Unsigned transaction export for signers that can't sign on the keeper host (e.g. a
+//! multisig admin), and submission of the transactions they signed offline.
+
+use anyhow::{Context, Result, bail};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::json;
+use solana_nonce::{state::State as NonceState, versions::Versions as NonceVersions};
+use solana_sdk::{
+    hash::Hash,
+    instruction::Instruction,
+    pubkey::Pubkey,
+    signature::Signature,
+    transaction::{Transaction, VersionedTransaction},
+};
+use solana_system_interface::{instruction::advance_nonce_account, program as system_program};
+use std::str::FromStr;
+use tracing::warn;
+
+use crate::client::{pool::EndpointRole, rpc::Rpc};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ExportFormat {
+    /// Unsigned legacy transaction, bincode serialized, base58
+    Base58,
+    /// Unsigned legacy transaction, bincode serialized, base64
+    Base64,
+    /// The raw instruction: program id, account metas and base64 data
+    Json,
+}
+
+impl FromStr for ExportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "base58" => Ok(Self::Base58),
+            "base64" => Ok(Self::Base64),
+            "json" => Ok(Self::Json),
+            other => bail!("export format must be base58|base64|json, got {}", other),
+        }
+    }
+}
+
+/// Serialize an instruction for an offline signer, with `fee_payer` paying the fees
+///
+/// With a `nonce_account` the transaction formats advance that durable nonce first and use
+/// its value as blockhash, so they stay valid until the nonce is used: the way to collect
+/// multisig signatures. Without one they carry a blockhash fetched now and must be signed
+/// and submitted before it expires (about a minute). The `Json` instruction is for multisig
+/// programs that build their own transaction.
+pub fn export_instruction(
+    rpc: &Rpc,
+    fee_payer: &Pubkey,
+    instruction: &Instruction,
+    format: ExportFormat,
+    nonce_account: Option<&Pubkey>,
+) -> Result<String> {
+    if format == ExportFormat::Json {
+        let accounts: Vec<_> = instruction
+            .accounts
+            .iter()
+            .map(|meta| {
+                json!({
+                    "pubkey": meta.pubkey.to_string(),
+                    "is_signer": meta.is_signer,
+                    "is_writable": meta.is_writable,
+                })
+            })
+            .collect();
+        let out = json!({
+            "program_id": instruction.program_id.to_string(),
+            "accounts": accounts,
+            "data": STANDARD.encode(&instruction.data),
+        });
+        return serde_json::to_string_pretty(&out).context("Failed to serialize instruction");
+    }
+
+    let (ixs, blockhash) = match nonce_account {
+        Some(nonce_account) => {
+            let (nonce, authority) = get_durable_nonce(rpc, nonce_account)?;
+            let advance = advance_nonce_account(nonce_account, &authority);
+            (vec![advance, instruction.clone()], nonce)
+        }
+        None => {
+            warn!("exported transaction expires with its blockhash, sign it within a minute");
+            let blockhash = rpc.read("get_latest_blockhash", |c| c.get_latest_blockhash())?;
+            (vec![instruction.clone()], blockhash)
+        }
+    };
+    let mut tx = Transaction::new_with_payer(&ixs, Some(fee_payer));
+    tx.message.recent_blockhash = blockhash;
+    let bytes = bincode::serialize(&tx).context("Failed to serialize transaction")?;
+
+    Ok(match format {
+        ExportFormat::Base58 => bs58::encode(bytes).into_string(),
+        _ => STANDARD.encode(bytes),
+    })
+}
+
+/// Current value and authority of a durable nonce account
+fn get_durable_nonce(rpc: &Rpc, nonce_account: &Pubkey) -> Result<(Hash, Pubkey)> {
+    let acc = rpc
+        .read("get_account", |c| c.get_account(nonce_account))
+        .with_context(|| format!("Failed to fetch nonce account {}", nonce_account))?;
+    if acc.owner != system_program::ID {
+        bail!(
+            "Nonce account {} is owned by {}, not the system program",
+            nonce_account,
+            acc.owner
+        );
+    }
+
+    let versions: NonceVersions = bincode::deserialize(&acc.data)
+        .with_context(|| format!("{} is not a nonce account", nonce_account))?;
+    match versions.state() {
+        NonceState::Initialized(data) => Ok((data.blockhash(), data.authority)),
+        NonceState::Uninitialized => bail!("Nonce account {} is not initialized", nonce_account),
+    }
+}
+
+/// Decode a base64 or base58 serialized transaction and check it is fully signed
+pub fn decode_signed_transaction(raw: &str) -> Result<VersionedTransaction> {
+    let raw = raw.trim();
+    // base58 text can also be valid base64, so the decoded bytes decide
+    let tx: VersionedTransaction = STANDARD
+        .decode(raw)
+        .ok()
+        .and_then(|bytes| bincode::deserialize(&bytes).ok())
+        .or_else(|| {
+            bs58::decode(raw)
+                .into_vec()
+                .ok()
+                .and_then(|bytes| bincode::deserialize(&bytes).ok())
+        })
+        .context("Not a base64 or base58 serialized transaction")?;
+
+    let required = tx.message.header().num_required_signatures as usize;
+    if tx.signatures.len() != required {
+        bail!(
+            "Transaction has {} signatures, {} required",
+            tx.signatures.len(),
+            required
+        );
+    }
+    let unsigned: Vec<String> = tx
+        .message
+        .static_account_keys()
+        .iter()
+        .zip(tx.verify_with_results())
+        .filter(|(_, valid)| !valid)
+        .map(|(key, _)| key.to_string())
+        .collect();
+    if !unsigned.is_empty() {
+        bail!("Missing or invalid signature of {}", unsigned.join(", "));
+    }
+
+    Ok(tx)
+}
+
+/// Broadcast a transaction signed offline and wait for the configured commitment
+pub fn submit_signed_transaction(rpc: &Rpc, tx: &VersionedTransaction) -> Result<Signature> {
+    rpc.pool()
+        .call(EndpointRole::Send, "send_and_confirm_transaction", |c| {
+            c.send_and_confirm_transaction(tx)
+        })
+}
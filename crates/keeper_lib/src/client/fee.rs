@@ -0,0 +1,148 @@
+use anyhow::{Result, bail};
+use solana_client::rpc_client::RpcClient;
+use solana_sdk::{instruction::Instruction, pubkey::Pubkey};
+use tracing::{debug, warn};
+
+/// Max number of accounts accepted by `getRecentPrioritizationFees`
+const MAX_FEE_ACCOUNTS: usize = 128;
+
+/// How the compute unit price is picked for each send attempt
+#[derive(Clone, Debug)]
+pub enum PriorityFeeStrategy {
+    /// Always use the same price
+    Static {
+        micro_lamports: u64,
+        max_micro_lamports: u64,
+    },
+    /// Use a percentile of the recent prioritization fees paid for the writable accounts of the tx
+    Percentile {
+        percentile: u8,
+        min_micro_lamports: u64,
+        max_micro_lamports: u64,
+    },
+    /// Start from a base price and raise it by `step_bps` of the base on every retry
+    Escalating {
+        base_micro_lamports: u64,
+        step_bps: u64,
+        max_micro_lamports: u64,
+    },
+}
+
+impl PriorityFeeStrategy {
+    /// Build a strategy from its mode name (`static`, `percentile` or `escalate`)
+    pub fn from_mode(
+        mode: &str,
+        base_micro_lamports: u64,
+        percentile: u8,
+        step_bps: u64,
+        max_micro_lamports: u64,
+    ) -> Result<Self> {
+        if percentile > 100 {
+            bail!("priority fee percentile must be between 0 and 100");
+        }
+
+        let strategy = match mode.to_lowercase().as_str() {
+            "static" => Self::Static {
+                micro_lamports: base_micro_lamports,
+                max_micro_lamports,
+            },
+            "percentile" => Self::Percentile {
+                percentile,
+                min_micro_lamports: base_micro_lamports,
+                max_micro_lamports,
+            },
+            "escalate" => Self::Escalating {
+                base_micro_lamports,
+                step_bps,
+                max_micro_lamports,
+            },
+            other => bail!("unknown priority fee strategy: {}", other),
+        };
+
+        Ok(strategy)
+    }
+
+    /// Upper bound of the price this strategy will ever pick
+    pub fn ceiling(&self) -> u64 {
+        match self {
+            Self::Static {
+                max_micro_lamports, ..
+            }
+            | Self::Percentile {
+                max_micro_lamports, ..
+            }
+            | Self::Escalating {
+                max_micro_lamports, ..
+            } => *max_micro_lamports,
+        }
+    }
+
+    /// Compute unit price for the given (1-based) attempt, capped at the ceiling
+    pub fn price_for_attempt(
+        &self,
+        client: &RpcClient,
+        ixs: &[Instruction],
+        attempt: usize,
+    ) -> u64 {
+        let price = match self {
+            Self::Static { micro_lamports, .. } => *micro_lamports,
+            Self::Percentile {
+                percentile,
+                min_micro_lamports,
+                ..
+            } => {
+                let accounts = writable_accounts(ixs);
+                match client.get_recent_prioritization_fees(&accounts) {
+                    Ok(fees) => {
+                        let fees: Vec<u64> = fees.iter().map(|f| f.prioritization_fee).collect();
+                        let fee = fee_percentile(fees, *percentile);
+                        debug!(
+                            accounts = accounts.len(),
+                            percentile, fee, "recent prioritization fee"
+                        );
+                        fee.max(*min_micro_lamports)
+                    }
+                    Err(e) => {
+                        warn!(error = %e, "get_recent_prioritization_fees failed, using minimum fee");
+                        *min_micro_lamports
+                    }
+                }
+            }
+            Self::Escalating {
+                base_micro_lamports,
+                step_bps,
+                ..
+            } => {
+                let retries = attempt.saturating_sub(1) as u64;
+                let step = base_micro_lamports.saturating_mul(*step_bps) / 10_000;
+                base_micro_lamports.saturating_add(step.saturating_mul(retries))
+            }
+        };
+
+        price.min(self.ceiling())
+    }
+}
+
+/// Writable, non-signer accounts touched by the instructions (round PDA, vault, ...)
+fn writable_accounts(ixs: &[Instruction]) -> Vec<Pubkey> {
+    let mut out: Vec<Pubkey> = Vec::new();
+    for meta in ixs.iter().flat_map(|ix| ix.accounts.iter()) {
+        if meta.is_writable && !meta.is_signer && !out.contains(&meta.pubkey) {
+            out.push(meta.pubkey);
+        }
+        if out.len() == MAX_FEE_ACCOUNTS {
+            break;
+        }
+    }
+    out
+}
+
+/// Nearest-rank percentile of the given fees (0 if there are none)
+fn fee_percentile(mut fees: Vec<u64>, percentile: u8) -> u64 {
+    if fees.is_empty() {
+        return 0;
+    }
+    fees.sort_unstable();
+    let rank = (fees.len() * percentile as usize).div_ceil(100).max(1);
+    fees[rank - 1]
+}
@@ -0,0 +1,323 @@
+use anyhow::{Context, Result, anyhow, bail};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    nonblocking::rpc_client::RpcClient as NonblockingRpcClient,
+    rpc_client::RpcClient,
+};
+use solana_commitment_config::CommitmentConfig;
+use std::{
+    future::Future,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+/// Weight of the newest sample in the latency/error moving averages
+const EWMA_ALPHA: f64 = 0.2;
+/// Consecutive failures after which an endpoint is demoted
+const DEMOTE_AFTER_FAILURES: u32 = 3;
+
+/// What an endpoint is used for
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EndpointRole {
+    Read,
+    Send,
+    Both,
+}
+
+impl EndpointRole {
+    fn serves(&self, role: EndpointRole) -> bool {
+        *self == EndpointRole::Both || *self == role
+    }
+}
+
+/// RPC endpoint definition
+#[derive(Clone, Debug)]
+pub struct RpcEndpoint {
+    pub url: String,
+    pub role: EndpointRole,
+}
+
+impl RpcEndpoint {
+    /// Parse a comma separated list of `url|role` entries (role defaults to `both`)
+    pub fn parse_list(s: &str) -> Result<Vec<Self>> {
+        let mut out = Vec::new();
+        for entry in s.split(',').map(str::trim).filter(|e| !e.is_empty()) {
+            let (url, role) = match entry.rsplit_once('|') {
+                Some((url, role)) => (url.trim(), role.trim()),
+                None => (entry, "both"),
+            };
+            let role = match role.to_lowercase().as_str() {
+                "read" => EndpointRole::Read,
+                "send" => EndpointRole::Send,
+                "both" => EndpointRole::Both,
+                other => bail!("invalid endpoint role {} for {}", other, url),
+            };
+            out.push(Self {
+                url: url.to_string(),
+                role,
+            });
+        }
+        if out.is_empty() {
+            bail!("no RPC endpoints configured");
+        }
+        Ok(out)
+    }
+}
+
+#[derive(Debug)]
+struct Health {
+    latency_ms: f64,
+    error_rate: f64,
+    consecutive_failures: u32,
+    demoted_until: Option<Instant>,
+}
+
+impl Health {
+    /// Lower is better: latency inflated by the recent error rate
+    fn score(&self) -> f64 {
+        self.latency_ms * (1.0 + 10.0 * self.error_rate)
+    }
+}
+
+pub struct Endpoint {
+    label: String,
+    role: EndpointRole,
+    client: RpcClient,
+    nonblocking: NonblockingRpcClient,
+    health: Mutex<Health>,
+}
+
+impl Endpoint {
+    /// Host of the endpoint (query strings may carry API keys, so the full URL is never logged)
+    pub fn label(&self) -> &str {
+        &self.label
+    }
+
+    pub fn role(&self) -> EndpointRole {
+        self.role
+    }
+
+    pub fn client(&self) -> &RpcClient {
+        &self.client
+    }
+
+    pub fn nonblocking_client(&self) -> &NonblockingRpcClient {
+        &self.nonblocking
+    }
+
+    fn is_demoted(&self) -> bool {
+        self.health.lock().unwrap().demoted_until.is_some()
+    }
+
+    fn score(&self) -> f64 {
+        self.health.lock().unwrap().score()
+    }
+}
+
+/// Set of RPC endpoints with health scoring and failover
+pub struct RpcPool {
+    endpoints: Vec<Endpoint>,
+    probe_interval: Duration,
+}
+
+impl RpcPool {
+    pub fn new(
+        endpoints: Vec<RpcEndpoint>,
+        timeout: Duration,
+        commitment_cfg: CommitmentConfig,
+        probe_interval: Duration,
+    ) -> Self {
+        let endpoints = endpoints
+            .into_iter()
+            .map(|ep| Endpoint {
+                label: endpoint_label(&ep.url),
+                role: ep.role,
+                nonblocking: NonblockingRpcClient::new_with_timeout_and_commitment(
+                    ep.url.clone(),
+                    timeout,
+                    commitment_cfg,
+                ),
+                client: RpcClient::new_with_timeout_and_commitment(ep.url, timeout, commitment_cfg),
+                health: Mutex::new(Health {
+                    latency_ms: 0.0,
+                    error_rate: 0.0,
+                    consecutive_failures: 0,
+                    demoted_until: None,
+                }),
+            })
+            .collect();
+
+        Self {
+            endpoints,
+            probe_interval,
+        }
+    }
+
+    /// Best endpoint for the given role
+    pub fn pick(&self, role: EndpointRole) -> &Endpoint {
+        self.ranked(role)[0]
+    }
+
+    /// Endpoints ordered by preference: healthy role matches, then by score
+    ///
+    /// Demoted endpoints are only restored by a successful call or `reprobe_demoted`.
+    pub fn ranked(&self, role: EndpointRole) -> Vec<&Endpoint> {
+        let mut ranked: Vec<&Endpoint> = self.endpoints.iter().collect();
+        ranked.sort_by(|a, b| {
+            let key = |e: &Endpoint| (e.is_demoted(), !e.role.serves(role));
+            key(a).cmp(&key(b)).then(a.score().total_cmp(&b.score()))
+        });
+        ranked
+    }
+
+    /// Run a call against the ranked endpoints until one succeeds
+    pub fn call<T, F>(&self, role: EndpointRole, op: &str, f: F) -> Result<T>
+    where
+        F: Fn(&RpcClient) -> Result<T, ClientError>,
+    {
+        let mut last_err = None;
+        for ep in self.ranked(role) {
+            let started = Instant::now();
+            match f(&ep.client) {
+                Ok(v) => {
+                    self.record(ep, started.elapsed(), true);
+                    debug!(endpoint = ep.label(), op, "rpc call ok");
+                    return Ok(v);
+                }
+                Err(e) if !is_transport_error(&e) => {
+                    // The endpoint answered, another one would give the same answer
+                    self.record(ep, started.elapsed(), true);
+                    return Err(anyhow::Error::from(e)).with_context(|| format!("{} failed", op));
+                }
+                Err(e) => {
+                    self.record(ep, started.elapsed(), false);
+                    warn!(endpoint = ep.label(), op, error = %e, "rpc call failed, failing over");
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        match last_err {
+            Some(e) => Err(anyhow::Error::from(e)).with_context(|| format!("{} failed", op)),
+            None => Err(anyhow!("{} failed: no endpoints", op)),
+        }
+    }
+
+    /// Async `call` using the endpoints' nonblocking clients
+    pub async fn call_async<'a, T, F, Fut>(
+        &'a self,
+        role: EndpointRole,
+        op: &str,
+        f: F,
+    ) -> Result<T>
+    where
+        F: Fn(&'a NonblockingRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        let mut last_err = None;
+        for ep in self.ranked(role) {
+            let started = Instant::now();
+            match f(&ep.nonblocking).await {
+                Ok(v) => {
+                    self.record(ep, started.elapsed(), true);
+                    debug!(endpoint = ep.label(), op, "rpc call ok");
+                    return Ok(v);
+                }
+                Err(e) if !is_transport_error(&e) => {
+                    self.record(ep, started.elapsed(), true);
+                    return Err(anyhow::Error::from(e)).with_context(|| format!("{} failed", op));
+                }
+                Err(e) => {
+                    self.record(ep, started.elapsed(), false);
+                    warn!(endpoint = ep.label(), op, error = %e, "rpc call failed, failing over");
+                    last_err = Some(e);
+                }
+            }
+        }
+
+        match last_err {
+            Some(e) => Err(anyhow::Error::from(e)).with_context(|| format!("{} failed", op)),
+            None => Err(anyhow!("{} failed: no endpoints", op)),
+        }
+    }
+
+    /// Record the outcome of a call made with an endpoint's client
+    pub fn record(&self, ep: &Endpoint, latency: Duration, ok: bool) {
+        let mut h = ep.health.lock().unwrap();
+        let sample_ms = latency.as_secs_f64() * 1_000.0;
+        h.latency_ms = if h.latency_ms == 0.0 {
+            sample_ms
+        } else {
+            EWMA_ALPHA * sample_ms + (1.0 - EWMA_ALPHA) * h.latency_ms
+        };
+        let err_sample = if ok { 0.0 } else { 1.0 };
+        h.error_rate = EWMA_ALPHA * err_sample + (1.0 - EWMA_ALPHA) * h.error_rate;
+
+        if ok {
+            h.consecutive_failures = 0;
+            if h.demoted_until.take().is_some() {
+                info!(endpoint = ep.label(), "rpc endpoint restored");
+            }
+        } else {
+            h.consecutive_failures = h.consecutive_failures.saturating_add(1);
+            if h.consecutive_failures >= DEMOTE_AFTER_FAILURES && h.demoted_until.is_none() {
+                h.demoted_until = Some(Instant::now() + self.probe_interval);
+                warn!(
+                    endpoint = ep.label(),
+                    error_rate = h.error_rate,
+                    "rpc endpoint demoted"
+                );
+            }
+        }
+    }
+
+    /// Interval between probes of a demoted endpoint
+    pub fn probe_interval(&self) -> Duration {
+        self.probe_interval
+    }
+
+    /// Probe demoted endpoints whose probe interval elapsed, restoring those that answer
+    ///
+    /// Runs on the nonblocking clients, off the call path, so a hung endpoint only delays
+    /// its own probe.
+    pub async fn reprobe_demoted(&self) {
+        for ep in &self.endpoints {
+            let due = matches!(
+                ep.health.lock().unwrap().demoted_until,
+                Some(until) if until <= Instant::now()
+            );
+            if !due {
+                continue;
+            }
+
+            let started = Instant::now();
+            let ok = ep.nonblocking.get_slot().await.is_ok();
+            if ok {
+                self.record(ep, started.elapsed(), true);
+            } else {
+                let mut h = ep.health.lock().unwrap();
+                h.demoted_until = Some(Instant::now() + self.probe_interval);
+                debug!(endpoint = ep.label(), "rpc endpoint probe failed");
+            }
+        }
+    }
+}
+
+/// Whether the error comes from reaching the endpoint rather than from the request itself
+pub fn is_transport_error(e: &ClientError) -> bool {
+    matches!(
+        e.kind(),
+        ClientErrorKind::Io(_) | ClientErrorKind::Reqwest(_) | ClientErrorKind::Middleware(_)
+    )
+}
+
+/// Strip scheme, credentials, path and query from an endpoint URL
+fn endpoint_label(url: &str) -> String {
+    let rest = url.split_once("://").map(|(_, r)| r).unwrap_or(url);
+    let authority = rest.split(['/', '?']).next().unwrap_or(rest);
+    authority
+        .rsplit_once('@')
+        .map_or(authority, |(_, host)| host)
+        .to_string()
+}
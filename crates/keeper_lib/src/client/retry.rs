@@ -0,0 +1,65 @@
+use crate::{
+    client::pool::is_transport_error,
+    errors::{GoldRushError, decode_client_error},
+};
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::RpcError,
+};
+use solana_sdk::{instruction::InstructionError, transaction::TransactionError};
+
+/// JSON-RPC "invalid params" code, what the RPC answers for a tx over the packet size
+const JSON_RPC_INVALID_PARAMS: i64 = -32602;
+
+/// Whether a failed send is worth another attempt
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RetryClass {
+    /// RPC, network or blockhash trouble: back off and try again
+    Transient,
+    /// The program (or runtime) rejected the tx: retrying cannot succeed
+    Fatal,
+    /// Over the packet size, account lock or compute unit limits: the same tx can never land,
+    /// a smaller one may
+    TooLarge,
+}
+
+/// Classify a send error, decoding the Gold Rush program error if there is one
+pub fn classify(err: &ClientError) -> (RetryClass, Option<GoldRushError>) {
+    if let Some(program_err) = decode_client_error(err) {
+        return (RetryClass::Fatal, Some(program_err));
+    }
+
+    if is_transport_error(err) {
+        return (RetryClass::Transient, None);
+    }
+
+    // The RPC rejects oversized txs before preflight, with no tx error to match on
+    if let ClientErrorKind::RpcError(RpcError::RpcResponseError { code, message, .. }) = err.kind()
+        && *code == JSON_RPC_INVALID_PARAMS
+        && message.contains("too large")
+    {
+        return (RetryClass::TooLarge, None);
+    }
+
+    let class = match err.get_transaction_error() {
+        Some(ref e) if exceeds_tx_limits(e) => RetryClass::TooLarge,
+        Some(
+            TransactionError::InstructionError(..)
+            | TransactionError::InsufficientFundsForFee
+            | TransactionError::AccountNotFound
+            | TransactionError::InvalidAccountForFee,
+        ) => RetryClass::Fatal,
+        _ => RetryClass::Transient,
+    };
+
+    (class, None)
+}
+
+/// Whether a tx error means the tx is over the account lock or compute unit limits
+pub fn exceeds_tx_limits(err: &TransactionError) -> bool {
+    matches!(
+        err,
+        TransactionError::TooManyAccountLocks
+            | TransactionError::InstructionError(_, InstructionError::ComputationalBudgetExceeded)
+    )
+}
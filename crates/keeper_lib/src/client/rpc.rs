@@ -1,44 +1,83 @@
-use crate::storage::sqlite::{TxLog, get_tx_context, log_tx};
-use anyhow::{Context, Result};
-use solana_client::{rpc_client::RpcClient, rpc_config::RpcSendTransactionConfig};
+use crate::client::{
+    fee::PriorityFeeStrategy,
+    pool::{EndpointRole, RpcEndpoint, RpcPool, is_transport_error},
+    retry::{RetryClass, classify, exceeds_tx_limits},
+};
+use crate::errors::{TxTooLargeError, decode_transaction_error};
+use crate::storage::sqlite::{
+    SignatureLog, TxContext, TxLog, get_tx_context, log_signature, log_tx,
+};
+use crate::wallet::TxSigners;
+use anyhow::{Context, Result, bail};
+use solana_client::{
+    client_error::ClientError,
+    nonblocking::rpc_client::RpcClient as NonblockingRpcClient,
+    rpc_client::RpcClient,
+    rpc_config::{RpcSendTransactionConfig, RpcSimulateTransactionConfig, RpcTransactionConfig},
+};
 use solana_commitment_config::{CommitmentConfig, CommitmentLevel};
 use solana_compute_budget_interface::ComputeBudgetInstruction;
 use solana_sdk::{
+    hash::Hash,
     instruction::Instruction,
-    signature::{Keypair, Signature, Signer},
-    transaction::Transaction,
+    message::{AddressLookupTableAccount, Message, VersionedMessage, v0},
+    signature::Signature,
+    transaction::{Transaction, TransactionError, VersionedTransaction},
+};
+use std::{
+    future::Future,
+    thread::sleep,
+    time::{Duration, Instant},
 };
-use std::{thread::sleep, time::Duration};
 use tracing::{debug, warn};
 
+/// Hard cap on compute units per transaction enforced by the runtime
+const MAX_COMPUTE_UNIT_LIMIT: u32 = 1_400_000;
+/// Base fee charged per transaction signature
+const LAMPORTS_PER_SIGNATURE: u64 = 5_000;
+
+/// How the compute unit limit is set on each transaction
+#[derive(Clone, Debug)]
+pub enum ComputeUnitLimit {
+    /// Always request the same limit
+    Static(u32),
+    /// Simulate first and request the consumed units plus `margin_bps`,
+    /// falling back to `fallback` if simulation fails
+    Simulated { fallback: u32, margin_bps: u32 },
+}
+
 pub struct Rpc {
-    inner: RpcClient,
+    pool: RpcPool,
     commitment_cfg: CommitmentConfig,
     send_cfg: RpcSendTransactionConfig,
     max_retries: usize,
-    cu_limit: u32,
-    cu_price_micro_lamports: u64,
+    compute_units: ComputeUnitLimit,
+    priority_fee: PriorityFeeStrategy,
     backoff_ms: u64,
     max_remaining_accounts: usize,
+    max_lookup_remaining_accounts: usize,
 }
 
 impl Rpc {
     pub fn new(
-        rpc_url: &str,
+        endpoints: Vec<RpcEndpoint>,
         timeout_ms: u64,
+        probe_interval_secs: u64,
         commitment: CommitmentLevel,
         preflight: bool,
         max_retries: usize,
-        cu_limit: u32,
-        cu_price_micro_lamports: u64,
+        compute_units: ComputeUnitLimit,
+        priority_fee: PriorityFeeStrategy,
         backoff_ms: u64,
         max_remaining_accounts: usize,
+        max_lookup_remaining_accounts: usize,
     ) -> Self {
         let commitment_cfg = CommitmentConfig { commitment };
-        let inner = RpcClient::new_with_timeout_and_commitment(
-            rpc_url.to_string(),
+        let pool = RpcPool::new(
+            endpoints,
             Duration::from_millis(timeout_ms),
             commitment_cfg,
+            Duration::from_secs(probe_interval_secs),
         );
         let send_cfg = RpcSendTransactionConfig {
             skip_preflight: !preflight,
@@ -48,19 +87,42 @@ impl Rpc {
         };
 
         Self {
-            inner,
+            pool,
             commitment_cfg,
             send_cfg,
             max_retries,
-            cu_limit,
-            cu_price_micro_lamports,
+            compute_units,
+            priority_fee,
             backoff_ms,
             max_remaining_accounts,
+            max_lookup_remaining_accounts,
         }
     }
 
+    /// Client of the healthiest read endpoint
     pub fn client(&self) -> &RpcClient {
-        &self.inner
+        self.pool.pick(EndpointRole::Read).client()
+    }
+
+    pub fn pool(&self) -> &RpcPool {
+        &self.pool
+    }
+
+    /// Run a read call with failover across the read endpoints
+    pub fn read<T, F>(&self, op: &str, f: F) -> Result<T>
+    where
+        F: Fn(&RpcClient) -> Result<T, ClientError>,
+    {
+        self.pool.call(EndpointRole::Read, op, f)
+    }
+
+    /// Async `read` on the nonblocking clients, for callers running on a tokio runtime
+    pub async fn read_async<'a, T, F, Fut>(&'a self, op: &str, f: F) -> Result<T>
+    where
+        F: Fn(&'a NonblockingRpcClient) -> Fut,
+        Fut: Future<Output = Result<T, ClientError>>,
+    {
+        self.pool.call_async(EndpointRole::Read, op, f).await
     }
 
     pub fn commitment_cfg(&self) -> &CommitmentConfig {
@@ -71,12 +133,20 @@ impl Rpc {
         &self.send_cfg
     }
 
+    pub fn compute_units(&self) -> &ComputeUnitLimit {
+        &self.compute_units
+    }
+
+    /// Static limit, also used as the fallback when simulation fails
     pub fn cu_limit(&self) -> u32 {
-        self.cu_limit
+        match self.compute_units {
+            ComputeUnitLimit::Static(limit) => limit,
+            ComputeUnitLimit::Simulated { fallback, .. } => fallback,
+        }
     }
 
-    pub fn cu_price_micro_lamports(&self) -> u64 {
-        self.cu_price_micro_lamports
+    pub fn priority_fee(&self) -> &PriorityFeeStrategy {
+        &self.priority_fee
     }
 
     pub fn max_retries(&self) -> usize {
@@ -90,14 +160,33 @@ impl Rpc {
     pub fn max_remaining_accounts(&self) -> usize {
         self.max_remaining_accounts
     }
+
+    /// Remaining accounts per chunk, higher when they are resolved through a lookup table
+    pub fn max_remaining_accounts_for(
+        &self,
+        lookup_table: Option<&AddressLookupTableAccount>,
+    ) -> usize {
+        match lookup_table {
+            Some(_) => self.max_lookup_remaining_accounts,
+            None => self.max_remaining_accounts,
+        }
+    }
 }
 
 /// Send a transaction with retry logic
 ///
+/// Each attempt signs one transaction and rebroadcasts it until it confirms or its blockhash
+/// expires. A new transaction is only signed once every earlier signature is known not to
+/// have landed, so a chunk is never executed twice.
+///
+/// Transient RPC/network/blockhash errors are retried with linear backoff. Program errors
+/// are decoded into `GoldRushError` and returned right away, since a retry cannot succeed;
+/// so are txs over the size, account lock or compute limits (`TxTooLargeError`).
+///
 /// # Arguments
 ///
 /// * `rpc` - The RPC client to use
-/// * `payer` - The keypair to use as the transaction payer
+/// * `signers` - The fee payer and keeper authority signing the transaction
 /// * `ixs` - The instructions to execute
 ///
 /// # Returns
@@ -105,108 +194,506 @@ impl Rpc {
 /// The signature of the transaction
 pub fn send_tx_with_retry(
     rpc: &Rpc,
-    payer: &Keypair,
+    signers: &TxSigners,
+    ixs: Vec<Instruction>,
+) -> Result<Signature> {
+    send_v0_tx_with_retry(rpc, signers, ixs, &[])
+}
+
+/// Send a v0 transaction resolving accounts through the given lookup tables, with the same
+/// rebroadcast/retry behavior as `send_tx_with_retry`.
+///
+/// Without lookup tables a legacy transaction is sent.
+pub fn send_v0_tx_with_retry(
+    rpc: &Rpc,
+    signers: &TxSigners,
     mut ixs: Vec<Instruction>,
+    lookup_tables: &[AddressLookupTableAccount],
 ) -> Result<Signature> {
-    let client = rpc.client();
     let commitment_cfg = rpc.commitment_cfg().clone();
-    let send_cfg = rpc.send_cfg().clone();
-    let cu_limit = rpc.cu_limit();
-    let cu_price_micro_lamports = rpc.cu_price_micro_lamports();
+    let priority_fee = rpc.priority_fee();
     let max_retries = rpc.max_retries();
     let backoff_ms = rpc.backoff_ms();
 
+    let cu_limit = match rpc.compute_units() {
+        ComputeUnitLimit::Static(limit) => *limit,
+        ComputeUnitLimit::Simulated {
+            fallback,
+            margin_bps,
+        } => match simulate_units_consumed(rpc, signers, &ixs, lookup_tables) {
+            Ok(units) => {
+                let with_margin = units.saturating_mul(10_000 + *margin_bps as u64) / 10_000;
+                let limit = with_margin.min(MAX_COMPUTE_UNIT_LIMIT as u64) as u32;
+                debug!(
+                    units_consumed = units,
+                    cu_limit = limit,
+                    "simulated compute units"
+                );
+                limit
+            }
+            Err(e) => {
+                warn!(error = %e, fallback, "compute unit simulation failed, using static limit");
+                *fallback
+            }
+        },
+    };
+
     ixs.insert(
         0,
         ComputeBudgetInstruction::set_compute_unit_limit(cu_limit),
     );
-    ixs.insert(
-        0,
-        ComputeBudgetInstruction::set_compute_unit_price(cu_price_micro_lamports),
-    );
+
+    let ctx = get_tx_context();
+    let mut signed: Vec<SignedAttempt> = Vec::new();
+    // Signed tx that is still valid and may be rebroadcast instead of re-signing
+    let mut inflight: Option<SignedAttempt> = None;
 
     let mut last_err = None;
     for attempt in 1..=max_retries {
-        let bh = client
-            .get_latest_blockhash()
-            .context("get_latest_blockhash")?;
-        let tx = Transaction::new_signed_with_payer(&ixs, Some(&payer.pubkey()), &[payer], bh);
-
-        match client.send_and_confirm_transaction_with_spinner_and_config(
-            &tx,
-            commitment_cfg.clone(),
-            send_cfg.clone(),
-        ) {
-            Ok(sig) => {
-                let sig_s = sig.to_string();
-                let tail = if sig_s.len() > 12 {
-                    &sig_s[sig_s.len() - 12..]
-                } else {
-                    sig_s.as_str()
+        // Re-pick every attempt so a failing endpoint is skipped on the retry
+        let endpoint = rpc.pool().pick(EndpointRole::Send);
+        let client = endpoint.client();
+        let rpc_endpoint = endpoint.label();
+        let mut meta = AttemptMeta {
+            attempt,
+            backoff_ms: backoff_ms.saturating_mul(attempt as u64),
+            cu_limit,
+            cu_price_micro_lamports: 0,
+            rpc_endpoint,
+        };
+
+        // An earlier signature may have landed late; never re-sign if one did
+        if inflight.is_none() && !signed.is_empty() {
+            let sigs: Vec<Signature> = signed.iter().map(|s| s.sig).collect();
+            match client.get_signature_statuses_with_history(&sigs) {
+                Ok(res) => {
+                    let landed = signed.iter().zip(res.value).find_map(|(s, status)| {
+                        status
+                            .filter(|st| {
+                                st.err.is_some() || st.satisfies_commitment(commitment_cfg)
+                            })
+                            .map(|st| (s, st.err))
+                    });
+                    if let Some((prior, err)) = landed {
+                        meta.attempt = prior.attempt;
+                        meta.cu_price_micro_lamports = prior.cu_price_micro_lamports;
+                        return finish_landed(rpc, ctx.as_ref(), &signed, prior, err, &meta);
+                    }
+                }
+                Err(e) => {
+                    warn!(attempt, rpc_endpoint, error = %e, "prior signature status check failed, backing off");
+                    last_err = Some(anyhow::Error::from(e));
+                    sleep(Duration::from_millis(meta.backoff_ms));
+                    continue;
+                }
+            }
+        }
+
+        let current = match inflight.take() {
+            Some(current) => current,
+            None => {
+                // The price may change per attempt, so it is prepended to a fresh copy of the ixs
+                let cu_price_micro_lamports = priority_fee.price_for_attempt(client, &ixs, attempt);
+                let mut attempt_ixs = Vec::with_capacity(ixs.len() + 1);
+                attempt_ixs.push(ComputeBudgetInstruction::set_compute_unit_price(
+                    cu_price_micro_lamports,
+                ));
+                attempt_ixs.extend(ixs.iter().cloned());
+
+                let (bh, last_valid_block_height) = match client
+                    .get_latest_blockhash_with_commitment(commitment_cfg)
+                {
+                    Ok(v) => v,
+                    Err(e) => {
+                        warn!(attempt, rpc_endpoint, error = %e, "get_latest_blockhash failed, backing off");
+                        last_err = Some(anyhow::Error::from(e).context("get_latest_blockhash"));
+                        sleep(Duration::from_millis(meta.backoff_ms));
+                        continue;
+                    }
                 };
-                debug!(
+                let tx = build_tx(signers, &attempt_ixs, lookup_tables, bh)?;
+                let current = SignedAttempt {
+                    sig: tx.signatures[0],
+                    tx,
                     attempt,
-                    cu_limit,
+                    last_valid_block_height,
                     cu_price_micro_lamports,
-                    tx_sig_tail = tail,
-                    "tx confirmed"
-                );
-                if let Some(ctx) = get_tx_context() {
-                    log_tx(TxLog {
-                        keeper_type: ctx.keeper_type,
-                        keeper_instance_id: String::new(),
-                        op: ctx.op,
-                        round_id: ctx.round_id,
-                        group_id: ctx.group_id,
-                        range_start: ctx.range_start,
-                        range_end: ctx.range_end,
-                        transaction_signature: Some(sig.to_string()),
-                        status: "success".to_string(),
-                        error_message: None,
-                        attempt: attempt as i64,
-                        retry_count: (attempt - 1) as i64,
-                        backoff_ms: backoff_ms.saturating_mul(attempt as u64) as i64,
-                        gas_used: Some(cu_limit as i64),
-                        module: Some(module_path!().to_string()),
-                        file: Some(file!().to_string()),
-                        line: Some(line!() as i64),
-                    });
-                }
-                return Ok(sig);
+                };
+                signed.push(current.clone());
+                log_signature_status(ctx.as_ref(), &signed, &current, "sent");
+                current
+            }
+        };
+        meta.cu_price_micro_lamports = current.cu_price_micro_lamports;
+
+        let started = Instant::now();
+        let res = broadcast_until_expired(rpc, client, &current);
+        let transport_ok = res
+            .as_ref()
+            .map_or_else(|e| !is_transport_error(e), |_| true);
+        rpc.pool().record(endpoint, started.elapsed(), transport_ok);
+
+        match res {
+            Ok(Broadcast::Landed(err)) => {
+                return finish_landed(rpc, ctx.as_ref(), &signed, &current, err, &meta);
+            }
+            Ok(Broadcast::Expired) => {
+                warn!(attempt, rpc_endpoint, tx_sig = %current.sig, last_valid_block_height = current.last_valid_block_height, "blockhash expired before confirmation");
+                log_signature_status(ctx.as_ref(), &signed, &current, "expired");
+                last_err = Some(anyhow::anyhow!(
+                    "tx {} expired at block height {}",
+                    current.sig,
+                    current.last_valid_block_height
+                ));
             }
             Err(e) => {
-                warn!(attempt, backoff_ms = backoff_ms.saturating_mul(attempt as u64), error = %e, "tx attempt failed, backing off");
-                if attempt == max_retries {
-                    if let Some(ctx) = get_tx_context() {
-                        log_tx(TxLog {
-                            keeper_type: ctx.keeper_type,
-                            keeper_instance_id: String::new(),
-                            op: ctx.op,
-                            round_id: ctx.round_id,
-                            group_id: ctx.group_id,
-                            range_start: ctx.range_start,
-                            range_end: ctx.range_end,
-                            transaction_signature: None,
-                            status: "failed".to_string(),
-                            error_message: Some(format!("{:#}", e)),
-                            attempt: attempt as i64,
-                            retry_count: (attempt - 1) as i64,
-                            backoff_ms: backoff_ms.saturating_mul(attempt as u64) as i64,
-                            gas_used: Some(cu_limit as i64),
-                            module: Some(module_path!().to_string()),
-                            file: Some(file!().to_string()),
-                            line: Some(line!() as i64),
-                        });
-                    }
+                let (class, program_err) = classify(&e);
+                let fatal = class != RetryClass::Transient;
+                let error_name = program_err.map(|pe| pe.name());
+                warn!(attempt, rpc_endpoint, cu_price_micro_lamports = meta.cu_price_micro_lamports, fatal, error_name, backoff_ms = meta.backoff_ms, error = %e, "tx attempt failed");
+                if fatal || attempt == max_retries {
+                    log_attempt(
+                        ctx.as_ref(),
+                        &meta,
+                        None,
+                        None,
+                        Some((format!("{:#}", e), error_name)),
+                    );
                 }
-                last_err = Some(e);
-                sleep(Duration::from_millis(
-                    backoff_ms.saturating_mul(attempt as u64),
-                ));
-                continue;
+                if fatal {
+                    // Preflight rejected it, so the signature can never land
+                    log_signature_status(ctx.as_ref(), &signed, &current, "failed");
+                    let err = anyhow::Error::from(e);
+                    return Err(match (program_err, class) {
+                        (Some(pe), _) => {
+                            anyhow::Error::new(pe).context(format!("send_tx failed: {:#}", err))
+                        }
+                        (None, RetryClass::TooLarge) => anyhow::Error::new(TxTooLargeError {
+                            reason: format!("{:#}", err),
+                        })
+                        .context("send_tx failed"),
+                        (None, _) => err.context("send_tx failed with a non-retryable error"),
+                    });
+                }
+                // Keep the same signed tx; it is rebroadcast until its blockhash expires
+                inflight = Some(current);
+                last_err = Some(anyhow::Error::from(e));
             }
         }
+
+        sleep(Duration::from_millis(meta.backoff_ms));
     }
 
     Err(anyhow::anyhow!("send_tx exhausted retries: {:?}", last_err))
 }
+
+/// How often a signed tx is rebroadcast while waiting for confirmation
+const REBROADCAST_INTERVAL: Duration = Duration::from_secs(2);
+
+/// A signed transaction and what it was signed with
+#[derive(Clone)]
+struct SignedAttempt {
+    tx: VersionedTransaction,
+    sig: Signature,
+    attempt: usize,
+    last_valid_block_height: u64,
+    cu_price_micro_lamports: u64,
+}
+
+/// Per-attempt fields shared by the `transaction_logs` rows
+struct AttemptMeta<'a> {
+    attempt: usize,
+    backoff_ms: u64,
+    cu_limit: u32,
+    cu_price_micro_lamports: u64,
+    rpc_endpoint: &'a str,
+}
+
+enum Broadcast {
+    /// Reached the configured commitment, with the tx error if it failed on-chain
+    Landed(Option<TransactionError>),
+    /// The blockhash expired without the signature landing
+    Expired,
+}
+
+/// Broadcast the same signed tx until it lands or its blockhash expires
+fn broadcast_until_expired(
+    rpc: &Rpc,
+    client: &RpcClient,
+    signed: &SignedAttempt,
+) -> Result<Broadcast, ClientError> {
+    let commitment_cfg = *rpc.commitment_cfg();
+    let send_cfg = *rpc.send_cfg();
+
+    loop {
+        if let Err(e) = client.send_transaction_with_config(&signed.tx, send_cfg) {
+            // Rebroadcasting a tx that already landed fails preflight with AlreadyProcessed,
+            // so only errors that can never succeed stop the loop
+            if classify(&e).0 != RetryClass::Transient {
+                return Err(e);
+            }
+            debug!(tx_sig = %signed.sig, error = %e, "rebroadcast failed");
+        }
+
+        sleep(REBROADCAST_INTERVAL);
+
+        let expired = client.get_block_height()? > signed.last_valid_block_height;
+        let status = client
+            .get_signature_statuses(&[signed.sig])?
+            .value
+            .into_iter()
+            .next()
+            .flatten();
+
+        match status {
+            Some(st) if st.err.is_some() => {
+                return Ok(Broadcast::Landed(st.err));
+            }
+            Some(st) if st.satisfies_commitment(commitment_cfg) => {
+                return Ok(Broadcast::Landed(None));
+            }
+            // Landed but not at the configured commitment yet: keep polling
+            Some(_) => continue,
+            None if expired => return Ok(Broadcast::Expired),
+            None => continue,
+        }
+    }
+}
+
+/// Log the outcome of a signature that landed and turn it into the send result
+fn finish_landed(
+    rpc: &Rpc,
+    ctx: Option<&TxContext>,
+    signed: &[SignedAttempt],
+    landed: &SignedAttempt,
+    err: Option<TransactionError>,
+    meta: &AttemptMeta,
+) -> Result<Signature> {
+    let sig = landed.sig;
+    // Only fetched for the `transaction_logs` row
+    let units_consumed = ctx.and_then(|_| landed_units_consumed(rpc, &sig));
+    match err {
+        None => {
+            let sig_s = sig.to_string();
+            let tail = if sig_s.len() > 12 {
+                &sig_s[sig_s.len() - 12..]
+            } else {
+                sig_s.as_str()
+            };
+            debug!(
+                attempt = meta.attempt,
+                rpc_endpoint = meta.rpc_endpoint,
+                cu_limit = meta.cu_limit,
+                cu_price_micro_lamports = meta.cu_price_micro_lamports,
+                tx_sig_tail = tail,
+                "tx confirmed"
+            );
+            log_signature_status(ctx, signed, landed, "confirmed");
+            log_attempt(ctx, meta, Some(landed), units_consumed, None);
+            Ok(sig)
+        }
+        Some(tx_err) => {
+            let program_err = decode_transaction_error(&tx_err);
+            let error_name = program_err.map(|pe| pe.name());
+            warn!(attempt = meta.attempt, tx_sig = %sig, error_name, error = ?tx_err, "tx landed with an error");
+            log_signature_status(ctx, signed, landed, "failed");
+            log_attempt(
+                ctx,
+                meta,
+                Some(landed),
+                units_consumed,
+                Some((format!("{:?}", tx_err), error_name)),
+            );
+            let err = anyhow::anyhow!("tx {} failed: {:?}", sig, tx_err);
+            Err(match program_err {
+                Some(pe) => anyhow::Error::new(pe).context(format!("{:#}", err)),
+                None if exceeds_tx_limits(&tx_err) => anyhow::Error::new(TxTooLargeError {
+                    reason: format!("{:?}", tx_err),
+                })
+                .context(format!("tx {} failed", sig)),
+                None => err,
+            })
+        }
+    }
+}
+
+/// Write the `transaction_logs` row of an attempt (success when `error` is `None`)
+///
+/// `landed` is the transaction that reached the chain, if any; only those are charged a fee.
+/// `units_consumed` is what the landed transaction used, from its confirmed meta.
+fn log_attempt(
+    ctx: Option<&TxContext>,
+    meta: &AttemptMeta,
+    landed: Option<&SignedAttempt>,
+    units_consumed: Option<u64>,
+    error: Option<(String, Option<&str>)>,
+) {
+    let Some(ctx) = ctx.cloned() else {
+        return;
+    };
+    let (status, error_message, error_name) = match error {
+        None => ("success", None, None),
+        Some((msg, name)) => ("failed", Some(msg), name.map(str::to_string)),
+    };
+    log_tx(TxLog {
+        keeper_type: ctx.keeper_type,
+        keeper_instance_id: String::new(),
+        op: ctx.op,
+        round_id: ctx.round_id,
+        group_id: ctx.group_id,
+        range_start: ctx.range_start,
+        range_end: ctx.range_end,
+        transaction_signature: landed.map(|l| l.sig.to_string()),
+        status: status.to_string(),
+        error_message,
+        attempt: meta.attempt as i64,
+        retry_count: (meta.attempt - 1) as i64,
+        backoff_ms: meta.backoff_ms as i64,
+        gas_used: units_consumed.map(|u| u as i64),
+        cu_limit: Some(meta.cu_limit as i64),
+        priority_fee_micro_lamports: Some(meta.cu_price_micro_lamports as i64),
+        fee_lamports: landed.map(|l| fee_lamports(l, meta.cu_limit) as i64),
+        rpc_endpoint: Some(meta.rpc_endpoint.to_string()),
+        error_name,
+        module: Some(module_path!().to_string()),
+        file: Some(file!().to_string()),
+        line: Some(line!() as i64),
+    });
+}
+
+/// Compute units a landed tx consumed, from its confirmed meta (`None` if unavailable)
+fn landed_units_consumed(rpc: &Rpc, sig: &Signature) -> Option<u64> {
+    // getTransaction doesn't serve processed transactions
+    let commitment = match rpc.commitment_cfg().commitment {
+        CommitmentLevel::Processed => CommitmentLevel::Confirmed,
+        commitment => commitment,
+    };
+    let cfg = RpcTransactionConfig {
+        commitment: Some(CommitmentConfig { commitment }),
+        max_supported_transaction_version: Some(0),
+        ..Default::default()
+    };
+    match rpc.read("get_transaction", |c| {
+        c.get_transaction_with_config(sig, cfg)
+    }) {
+        Ok(tx) => tx.transaction.meta?.compute_units_consumed.into(),
+        Err(e) => {
+            debug!(tx_sig = %sig, error = %e, "failed to fetch landed compute units");
+            None
+        }
+    }
+}
+
+/// Fee charged for a landed tx: base fee per signature plus the priority fee on the CU limit
+fn fee_lamports(landed: &SignedAttempt, cu_limit: u32) -> u64 {
+    let signatures = landed.tx.signatures.len() as u64;
+    let priority = (cu_limit as u64 * landed.cu_price_micro_lamports).div_ceil(1_000_000);
+    signatures * LAMPORTS_PER_SIGNATURE + priority
+}
+
+/// Persist a signature of the operation (keyed by the operation's first signature)
+fn log_signature_status(
+    ctx: Option<&TxContext>,
+    signed: &[SignedAttempt],
+    current: &SignedAttempt,
+    status: &str,
+) {
+    let ctx = ctx.cloned().unwrap_or_else(|| TxContext {
+        keeper_type: "other".into(),
+        op: "unknown".into(),
+        ..Default::default()
+    });
+    let op_key = signed.first().map_or(current.sig, |s| s.sig);
+    log_signature(SignatureLog {
+        op_key: op_key.to_string(),
+        keeper_type: ctx.keeper_type,
+        op: ctx.op,
+        round_id: ctx.round_id,
+        group_id: ctx.group_id,
+        range_start: ctx.range_start,
+        range_end: ctx.range_end,
+        attempt: current.attempt as i64,
+        signature: current.sig.to_string(),
+        last_valid_block_height: current.last_valid_block_height as i64,
+        status: status.to_string(),
+    });
+}
+
+/// Simulate the instructions with the max compute limit and return the units consumed
+///
+/// The tx is simulated unsigned, so a remote signer is not asked to sign it.
+fn simulate_units_consumed(
+    rpc: &Rpc,
+    signers: &TxSigners,
+    ixs: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+) -> Result<u64> {
+    let mut sim_ixs = Vec::with_capacity(ixs.len() + 1);
+    sim_ixs.push(ComputeBudgetInstruction::set_compute_unit_limit(
+        MAX_COMPUTE_UNIT_LIMIT,
+    ));
+    sim_ixs.extend(ixs.iter().cloned());
+
+    // The blockhash is replaced by the RPC
+    let fee_payer = signers.fee_payer_pubkey();
+    let message = if lookup_tables.is_empty() {
+        VersionedMessage::Legacy(Message::new(&sim_ixs, Some(&fee_payer)))
+    } else {
+        let message =
+            v0::Message::try_compile(&fee_payer, &sim_ixs, lookup_tables, Hash::default())
+                .context("Failed to compile v0 message")?;
+        VersionedMessage::V0(message)
+    };
+    let tx = VersionedTransaction {
+        signatures: vec![Signature::default(); message.header().num_required_signatures as usize],
+        message,
+    };
+
+    let sim_cfg = RpcSimulateTransactionConfig {
+        sig_verify: false,
+        replace_recent_blockhash: true,
+        commitment: Some(*rpc.commitment_cfg()),
+        ..Default::default()
+    };
+    let res = rpc
+        .read("simulate_transaction", |c| {
+            c.simulate_transaction_with_config(&tx, sim_cfg.clone())
+        })?
+        .value;
+
+    if let Some(err) = res.err {
+        bail!("simulation failed: {:?}", err);
+    }
+
+    res.units_consumed
+        .context("simulation did not report units consumed")
+}
+
+/// Sign a legacy transaction, or a v0 one when lookup tables are given
+///
+/// The fee payer always signs; the authority only when an instruction requires it.
+fn build_tx(
+    signers: &TxSigners,
+    ixs: &[Instruction],
+    lookup_tables: &[AddressLookupTableAccount],
+    blockhash: Hash,
+) -> Result<VersionedTransaction> {
+    let fee_payer = signers.fee_payer_pubkey();
+
+    if lookup_tables.is_empty() {
+        // `try_sign` instead of `new_signed_with_payer`, a remote signer can fail
+        let mut tx = Transaction::new_with_payer(ixs, Some(&fee_payer));
+        let num_signers = tx.message.header.num_required_signatures as usize;
+        let required = signers.required(&tx.message.account_keys[..num_signers]);
+        tx.try_sign(required.as_slice(), blockhash)
+            .context("Failed to sign transaction")?;
+        return Ok(VersionedTransaction::from(tx));
+    }
+
+    let message = v0::Message::try_compile(&fee_payer, ixs, lookup_tables, blockhash)
+        .context("Failed to compile v0 message")?;
+    let num_signers = message.header.num_required_signatures as usize;
+    let required = signers.required(&message.account_keys[..num_signers]);
+    VersionedTransaction::try_new(VersionedMessage::V0(message), required.as_slice())
+        .context("Failed to sign v0 transaction")
+}
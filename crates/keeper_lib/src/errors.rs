@@ -0,0 +1,176 @@
+use solana_client::{
+    client_error::{ClientError, ClientErrorKind},
+    rpc_request::{RpcError, RpcResponseErrorData},
+};
+use solana_sdk::{instruction::InstructionError, pubkey::Pubkey, transaction::TransactionError};
+use std::fmt;
+
+include!(concat!(env!("OUT_DIR"), "/gold_rush_errors.rs"));
+
+impl fmt::Display for GoldRushError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} ({}): {}", self.name(), self.code(), self.msg())
+    }
+}
+
+impl std::error::Error for GoldRushError {}
+
+/// Decode a program error from `InstructionError::Custom`
+pub fn decode_transaction_error(err: &TransactionError) -> Option<GoldRushError> {
+    match err {
+        TransactionError::InstructionError(_, InstructionError::Custom(code)) => {
+            GoldRushError::from_code(*code)
+        }
+        _ => None,
+    }
+}
+
+/// Decode a program error from Anchor log lines
+/// (`... Error Code: RoundNotEnded. Error Number: 18294. Error Message: ...`)
+pub fn decode_logs(logs: &[String]) -> Option<GoldRushError> {
+    logs.iter().find_map(|line| {
+        let rest = line.split("Error Number: ").nth(1)?;
+        let digits: String = rest.chars().take_while(|c| c.is_ascii_digit()).collect();
+        GoldRushError::from_code(digits.parse().ok()?)
+    })
+}
+
+/// Decode a program error from a `custom program error: 0x...` message
+fn decode_message(msg: &str) -> Option<GoldRushError> {
+    let rest = msg.split("custom program error: 0x").nth(1)?;
+    let hex: String = rest.chars().take_while(|c| c.is_ascii_hexdigit()).collect();
+    GoldRushError::from_code(u32::from_str_radix(&hex, 16).ok()?)
+}
+
+/// Decode a program error from an RPC client error (tx error, preflight logs or message)
+pub fn decode_client_error(err: &ClientError) -> Option<GoldRushError> {
+    if let Some(e) = err
+        .get_transaction_error()
+        .as_ref()
+        .and_then(decode_transaction_error)
+    {
+        return Some(e);
+    }
+
+    if let ClientErrorKind::RpcError(RpcError::RpcResponseError {
+        data: RpcResponseErrorData::SendTransactionPreflightFailure(sim),
+        ..
+    }) = err.kind()
+        && let Some(e) = sim.logs.as_deref().and_then(decode_logs)
+    {
+        return Some(e);
+    }
+
+    decode_message(&err.to_string())
+}
+
+/// A transaction over the packet size, account lock or compute unit limits
+///
+/// Returned without retrying, so a chunked send can shrink the chunk right away.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxTooLargeError {
+    pub reason: String,
+}
+
+impl fmt::Display for TxTooLargeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "tx too large: {}", self.reason)
+    }
+}
+
+impl std::error::Error for TxTooLargeError {}
+
+/// Why an account's data could not be decoded as the expected IDL account type
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The discriminator belongs to another account type
+    WrongType {
+        account: &'static str,
+        expected: [u8; 8],
+        found: [u8; 8],
+    },
+    /// The data is shorter than the 8 byte discriminator
+    Truncated { account: &'static str, len: usize },
+    /// The discriminator matches but the body does not deserialize (layout changed)
+    Layout {
+        account: &'static str,
+        reason: String,
+    },
+}
+
+impl DecodeError {
+    pub fn account(&self) -> &'static str {
+        match self {
+            DecodeError::WrongType { account, .. }
+            | DecodeError::Truncated { account, .. }
+            | DecodeError::Layout { account, .. } => account,
+        }
+    }
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::WrongType {
+                account,
+                expected,
+                found,
+            } => write!(
+                f,
+                "not a {} account: discriminator {:?}, expected {:?}",
+                account, found, expected
+            ),
+            DecodeError::Truncated { account, len } => {
+                write!(f, "{} account data truncated: {} bytes", account, len)
+            }
+            DecodeError::Layout { account, reason } => {
+                write!(f, "{} account layout mismatch: {}", account, reason)
+            }
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// A price feed whose latest update is older than the program accepts
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StaleFeed {
+    pub price_feed_account: Pubkey,
+    pub feed_id: [u8; 32],
+    pub publish_time: i64,
+    /// Seconds behind the on-chain clock
+    pub age_secs: i64,
+}
+
+/// Price feeds that would fail the program's `max_price_update_age_secs` check, found before
+/// sending
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StalePriceError {
+    pub max_age_secs: u64,
+    pub feeds: Vec<StaleFeed>,
+}
+
+impl fmt::Display for StalePriceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let feeds: Vec<String> = self
+            .feeds
+            .iter()
+            .map(|feed| {
+                format!(
+                    "{} (0x{}) published {}s ago",
+                    feed.price_feed_account,
+                    hex::encode(feed.feed_id),
+                    feed.age_secs
+                )
+            })
+            .collect();
+        write!(
+            f,
+            "stale feed, max age {}s: {}",
+            self.max_age_secs,
+            feeds.join(", ")
+        )
+    }
+}
+
+impl std::error::Error for StalePriceError {}
@@ -0,0 +1,7 @@
+//! Types, account discriminators and instruction builders generated by `build.rs` from
+//! `abi/gold_rush.json`, so any drift from the IDL fails to compile.
+//!
+//! Account data and instruction args hold anchor's `Pubkey`; instruction accounts take the
+//! `solana_sdk` one.
+
+include!(concat!(env!("OUT_DIR"), "/gold_rush_idl.rs"));
@@ -1,4 +1,6 @@
 pub mod client;
+pub mod errors;
+pub mod idl;
 pub mod pda;
 pub mod storage;
 pub mod types;
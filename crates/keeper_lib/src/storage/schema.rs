@@ -7,7 +7,7 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         CREATE TABLE IF NOT EXISTS transaction_logs (
             id INTEGER PRIMARY KEY AUTOINCREMENT,
             timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
-            keeper_type TEXT NOT NULL,                -- start|settle|other
+            keeper_type TEXT NOT NULL,                -- start|settle|cancel|create|admin|other
             keeper_instance_id TEXT NOT NULL,         -- hostname:pid or UUID
             op TEXT NOT NULL,                         -- operation kind, e.g. settle_bets_chunk
             round_id INTEGER,
@@ -15,12 +15,17 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
             range_start INTEGER,
             range_end INTEGER,
             transaction_signature TEXT,
-            status TEXT NOT NULL,                     -- success|failed
+            status TEXT NOT NULL,                     -- success|failed|skipped
             error_message TEXT,
             attempt INTEGER DEFAULT 0,
             retry_count INTEGER DEFAULT 0,
             backoff_ms INTEGER DEFAULT 0,
-            gas_used INTEGER,
+            gas_used INTEGER,                         -- compute units consumed by the landed tx
+            cu_limit INTEGER,
+            priority_fee_micro_lamports INTEGER,
+            fee_lamports INTEGER,                     -- fee charged if the tx landed (estimated)
+            rpc_endpoint TEXT,                        -- host of the endpoint used to send
+            error_name TEXT,                          -- decoded program error, e.g. roundNotEnded
             module TEXT,
             file TEXT,
             line INTEGER,
@@ -30,6 +35,18 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Columns added after the initial release (older databases lack them)
+    add_column_if_missing(conn, "transaction_logs", "cu_limit", "INTEGER")?;
+    add_column_if_missing(
+        conn,
+        "transaction_logs",
+        "priority_fee_micro_lamports",
+        "INTEGER",
+    )?;
+    add_column_if_missing(conn, "transaction_logs", "rpc_endpoint", "TEXT")?;
+    add_column_if_missing(conn, "transaction_logs", "error_name", "TEXT")?;
+    add_column_if_missing(conn, "transaction_logs", "fee_lamports", "INTEGER")?;
+
     // Indexes for common queries
     conn.execute(
         "CREATE INDEX IF NOT EXISTS idx_txlogs_ts ON transaction_logs(timestamp)",
@@ -48,5 +65,125 @@ pub fn create_tables(conn: &Connection) -> Result<()> {
         [],
     )?;
 
+    // Every signature signed for a logical send operation, so late landings can be traced
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS tx_signatures (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            op_key TEXT NOT NULL,                     -- first signature of the operation
+            keeper_type TEXT NOT NULL,
+            op TEXT NOT NULL,
+            round_id INTEGER,
+            group_id INTEGER,
+            range_start INTEGER,
+            range_end INTEGER,
+            attempt INTEGER NOT NULL,
+            signature TEXT NOT NULL UNIQUE,
+            last_valid_block_height INTEGER NOT NULL,
+            status TEXT NOT NULL,                     -- sent|confirmed|failed|expired
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_txsigs_op_key ON tx_signatures(op_key)",
+        [],
+    )?;
+
+    create_state_table(conn)?;
+    create_cancellation_table(conn)?;
+    create_program_pause_table(conn)?;
+
+    Ok(())
+}
+
+/// Small key/value state the keepers carry across restarts (e.g. the round watermark)
+pub fn create_state_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS keeper_state (
+            key TEXT PRIMARY KEY,
+            value INTEGER NOT NULL,
+            updated_at DATETIME DEFAULT CURRENT_TIMESTAMP
+        )
+        "#,
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Audit trail of rounds the keeper cancelled on its own, with the failure evidence
+pub fn create_cancellation_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS round_cancellations (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            timestamp DATETIME DEFAULT CURRENT_TIMESTAMP,
+            keeper_instance_id TEXT NOT NULL,
+            round_id INTEGER NOT NULL,
+            reason TEXT NOT NULL,                     -- e.g. settle_failures_past_grace
+            end_time INTEGER NOT NULL,                -- round end_time (unix)
+            overdue_secs INTEGER NOT NULL,            -- past end_time when the decision was made
+            failures INTEGER NOT NULL,                -- failed settle transactions since end_time
+            first_failure_at DATETIME,
+            last_failure_at DATETIME,
+            last_error TEXT,
+            last_error_name TEXT,
+            status TEXT NOT NULL,                     -- cancelled|failed
+            transaction_signature TEXT,
+            error_message TEXT
+        )
+        "#,
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_cancellations_round ON round_cancellations(round_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Periods each keeper saw the program paused, from `ConfigAccount::status`
+pub fn create_program_pause_table(conn: &Connection) -> Result<()> {
+    conn.execute(
+        r#"
+        CREATE TABLE IF NOT EXISTS program_pauses (
+            id INTEGER PRIMARY KEY AUTOINCREMENT,
+            keeper_instance_id TEXT NOT NULL,
+            status TEXT NOT NULL,                     -- Paused|EmergencyPaused
+            started_at INTEGER NOT NULL,              -- first seen paused (unix)
+            ended_at INTEGER,                         -- first seen not in `status` (unix), NULL while paused
+            duration_secs INTEGER
+        )
+        "#,
+        [],
+    )?;
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_program_pauses_open ON program_pauses(keeper_instance_id, ended_at)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+/// Add a column to an existing table unless it is already there
+fn add_column_if_missing(conn: &Connection, table: &str, column: &str, decl: &str) -> Result<()> {
+    let mut stmt = conn.prepare(&format!("PRAGMA table_info({})", table))?;
+    let exists = stmt
+        .query_map([], |row| row.get::<_, String>(1))?
+        .filter_map(|name| name.ok())
+        .any(|name| name == column);
+
+    if !exists {
+        conn.execute(
+            &format!("ALTER TABLE {} ADD COLUMN {} {}", table, column, decl),
+            [],
+        )?;
+    }
+
     Ok(())
 }
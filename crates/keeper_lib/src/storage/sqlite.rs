@@ -1,5 +1,7 @@
 use once_cell::sync::OnceCell;
-use rusqlite::{Connection, Result as SqlResult, params};
+use rusqlite::{
+    Connection, OpenFlags, OptionalExtension, Result as SqlResult, Transaction, params,
+};
 use std::cell::RefCell;
 use std::fs::create_dir_all;
 use std::path::Path;
@@ -7,7 +9,9 @@ use std::sync::mpsc::{SyncSender, sync_channel};
 use std::thread::{self, JoinHandle};
 use std::time::{Duration, Instant};
 
-use super::schema::create_tables;
+use super::schema::{
+    create_cancellation_table, create_program_pause_table, create_state_table, create_tables,
+};
 
 #[derive(Clone, Debug)]
 pub struct SQLiteLogConfig {
@@ -29,19 +33,47 @@ pub struct TxLog {
     pub range_start: Option<i64>,
     pub range_end: Option<i64>,
     pub transaction_signature: Option<String>,
-    pub status: String, // success|failed
+    pub status: String, // success|failed|skipped
     pub error_message: Option<String>,
     pub attempt: i64,
     pub retry_count: i64,
     pub backoff_ms: i64,
     pub gas_used: Option<i64>,
+    pub cu_limit: Option<i64>,
+    pub priority_fee_micro_lamports: Option<i64>,
+    pub fee_lamports: Option<i64>,
+    pub rpc_endpoint: Option<String>,
+    pub error_name: Option<String>,
     pub module: Option<String>,
     pub file: Option<String>,
     pub line: Option<i64>,
 }
 
+/// Every signature signed for a logical send operation (keyed by its first signature)
+#[derive(Clone, Debug)]
+pub struct SignatureLog {
+    pub op_key: String,
+    pub keeper_type: String,
+    pub op: String,
+    pub round_id: Option<i64>,
+    pub group_id: Option<i64>,
+    pub range_start: Option<i64>,
+    pub range_end: Option<i64>,
+    pub attempt: i64,
+    pub signature: String,
+    pub last_valid_block_height: i64,
+    pub status: String, // sent|confirmed|failed|expired
+}
+
+/// Records accepted by the background writer
+#[derive(Clone, Debug)]
+pub enum LogRecord {
+    Tx(Box<TxLog>),
+    Signature(Box<SignatureLog>),
+}
+
 pub struct SQLiteLogger {
-    tx: SyncSender<TxLog>,
+    tx: SyncSender<LogRecord>,
     _handle: JoinHandle<()>,
 }
 
@@ -50,7 +82,7 @@ static DEFAULT_INSTANCE_ID: OnceCell<String> = OnceCell::new();
 
 impl SQLiteLogger {
     pub fn start(cfg: SQLiteLogConfig) -> SqlResult<Self> {
-        let (tx, rx) = sync_channel::<TxLog>(cfg.queue_cap);
+        let (tx, rx) = sync_channel::<LogRecord>(cfg.queue_cap);
 
         let handle = thread::spawn(move || {
             // Ensure parent directory exists
@@ -65,7 +97,7 @@ impl SQLiteLogger {
 
             create_tables(&conn).expect("create tables");
 
-            let mut buffer: Vec<TxLog> = Vec::with_capacity(cfg.batch_max);
+            let mut buffer: Vec<LogRecord> = Vec::with_capacity(cfg.batch_max);
             let mut last_flush = Instant::now();
             let flush_interval = Duration::from_millis(cfg.batch_ms);
             let mut last_retention = Instant::now();
@@ -85,40 +117,8 @@ impl SQLiteLogger {
 
                 if !buffer.is_empty() && (need_time_flush || need_size_flush) {
                     let tx = conn.transaction().expect("begin tx");
-                    {
-                        let mut stmt = tx
-                            .prepare(
-                                "INSERT INTO transaction_logs (
-                                    keeper_type, keeper_instance_id, op, round_id, group_id,
-                                    range_start, range_end, transaction_signature, status,
-                                    error_message, attempt, retry_count, backoff_ms,
-                                    gas_used, module, file, line
-                                ) VALUES (
-                                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
-                                )",
-                            )
-                            .expect("prepare insert");
-                        for e in buffer.drain(..) {
-                            let _ = stmt.execute(params![
-                                e.keeper_type,
-                                e.keeper_instance_id,
-                                e.op,
-                                e.round_id,
-                                e.group_id,
-                                e.range_start,
-                                e.range_end,
-                                e.transaction_signature,
-                                e.status,
-                                e.error_message,
-                                e.attempt,
-                                e.retry_count,
-                                e.backoff_ms,
-                                e.gas_used,
-                                e.module,
-                                e.file,
-                                e.line,
-                            ]);
-                        }
+                    for record in buffer.drain(..) {
+                        let _ = insert_record(&tx, &record);
                     }
                     let _ = tx.commit();
                     last_flush = Instant::now();
@@ -129,6 +129,10 @@ impl SQLiteLogger {
                             "DELETE FROM transaction_logs WHERE timestamp < datetime('now', ?)",
                             [format!("-{} days", cfg.retention_days)],
                         );
+                        let _ = conn.execute(
+                            "DELETE FROM tx_signatures WHERE timestamp < datetime('now', ?)",
+                            [format!("-{} days", cfg.retention_days)],
+                        );
                         last_retention = Instant::now();
                     }
 
@@ -148,11 +152,78 @@ impl SQLiteLogger {
         })
     }
 
-    pub fn send(&self, entry: TxLog) -> Result<(), std::sync::mpsc::SendError<TxLog>> {
+    pub fn send(&self, entry: LogRecord) -> Result<(), std::sync::mpsc::SendError<LogRecord>> {
         self.tx.send(entry)
     }
 }
 
+fn insert_record(tx: &Transaction, record: &LogRecord) -> SqlResult<usize> {
+    match record {
+        LogRecord::Tx(e) => tx
+            .prepare_cached(
+                "INSERT INTO transaction_logs (
+                    keeper_type, keeper_instance_id, op, round_id, group_id,
+                    range_start, range_end, transaction_signature, status,
+                    error_message, attempt, retry_count, backoff_ms,
+                    gas_used, cu_limit, priority_fee_micro_lamports, fee_lamports,
+                    rpc_endpoint, error_name, module, file, line
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                )",
+            )?
+            .execute(params![
+                e.keeper_type,
+                e.keeper_instance_id,
+                e.op,
+                e.round_id,
+                e.group_id,
+                e.range_start,
+                e.range_end,
+                e.transaction_signature,
+                e.status,
+                e.error_message,
+                e.attempt,
+                e.retry_count,
+                e.backoff_ms,
+                e.gas_used,
+                e.cu_limit,
+                e.priority_fee_micro_lamports,
+                e.fee_lamports,
+                e.rpc_endpoint,
+                e.error_name,
+                e.module,
+                e.file,
+                e.line,
+            ]),
+        // One row per signature, the status is updated as the signature resolves
+        LogRecord::Signature(e) => tx
+            .prepare_cached(
+                "INSERT INTO tx_signatures (
+                    op_key, keeper_type, op, round_id, group_id, range_start,
+                    range_end, attempt, signature, last_valid_block_height, status
+                ) VALUES (
+                    ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?
+                )
+                ON CONFLICT(signature) DO UPDATE SET
+                    status = excluded.status,
+                    updated_at = CURRENT_TIMESTAMP",
+            )?
+            .execute(params![
+                e.op_key,
+                e.keeper_type,
+                e.op,
+                e.round_id,
+                e.group_id,
+                e.range_start,
+                e.range_end,
+                e.attempt,
+                e.signature,
+                e.last_valid_block_height,
+                e.status,
+            ]),
+    }
+}
+
 pub fn init_global_logger(cfg: SQLiteLogConfig) {
     let logger = SQLiteLogger::start(cfg).expect("start sqlite logger");
     let _ = GLOBAL_LOGGER.set(logger);
@@ -166,8 +237,271 @@ pub fn log_tx(entry: TxLog) {
                 e.keeper_instance_id = default_id.clone();
             }
         }
-        let _ = logger.send(e);
+        let _ = logger.send(LogRecord::Tx(Box::new(e)));
+    }
+}
+
+/// Log an operation skipped before anything was sent (`status = 'skipped'`)
+pub fn log_skipped(ctx: TxContext, error_name: &str, error_message: String) {
+    log_tx(TxLog {
+        keeper_type: ctx.keeper_type,
+        keeper_instance_id: String::new(),
+        op: ctx.op,
+        round_id: ctx.round_id,
+        group_id: ctx.group_id,
+        range_start: ctx.range_start,
+        range_end: ctx.range_end,
+        transaction_signature: None,
+        status: "skipped".to_string(),
+        error_message: Some(error_message),
+        attempt: 0,
+        retry_count: 0,
+        backoff_ms: 0,
+        gas_used: None,
+        cu_limit: None,
+        priority_fee_micro_lamports: None,
+        fee_lamports: None,
+        rpc_endpoint: None,
+        error_name: Some(error_name.to_string()),
+        module: Some(module_path!().to_string()),
+        file: Some(file!().to_string()),
+        line: Some(line!() as i64),
+    });
+}
+
+pub fn log_signature(entry: SignatureLog) {
+    if let Some(logger) = GLOBAL_LOGGER.get() {
+        let _ = logger.send(LogRecord::Signature(Box::new(entry)));
+    }
+}
+
+/// Fees recorded in `transaction_logs` over a time window
+#[derive(Clone, Copy, Debug, Default)]
+pub struct FeeSpend {
+    pub lamports: u64,
+    pub txs: u64,
+}
+
+/// Sum the fees of landed transactions logged in the last `window_secs`
+///
+/// Opens its own read-only connection, the writer thread keeps the other one.
+pub fn fee_spend_since(path: &str, window_secs: u64) -> SqlResult<FeeSpend> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    let _ = conn.pragma_update(None, "busy_timeout", 5000);
+    conn.query_row(
+        "SELECT COALESCE(SUM(fee_lamports), 0), COUNT(fee_lamports)
+         FROM transaction_logs
+         WHERE fee_lamports IS NOT NULL AND timestamp >= datetime('now', ?)",
+        [format!("-{} seconds", window_secs)],
+        |row| {
+            Ok(FeeSpend {
+                lamports: row.get::<_, i64>(0)? as u64,
+                txs: row.get::<_, i64>(1)? as u64,
+            })
+        },
+    )
+}
+
+/// Failed (or skipped, e.g. on a stale feed) settle attempts of a round, the evidence for
+/// cancelling it
+#[derive(Clone, Debug, Default)]
+pub struct SettleFailures {
+    pub count: u64,
+    pub first_at: Option<String>,
+    pub last_at: Option<String>,
+    pub last_error: Option<String>,
+    pub last_error_name: Option<String>,
+}
+
+/// Failed or skipped settle attempts logged for the round since `since` (unix seconds)
+pub fn settle_failures_since(path: &str, round_id: u64, since: i64) -> SqlResult<SettleFailures> {
+    let conn = Connection::open_with_flags(
+        path,
+        OpenFlags::SQLITE_OPEN_READ_ONLY | OpenFlags::SQLITE_OPEN_NO_MUTEX,
+    )?;
+    let _ = conn.pragma_update(None, "busy_timeout", 5000);
+
+    let filter = "FROM transaction_logs
+         WHERE keeper_type = 'settle' AND status IN ('failed', 'skipped') AND round_id = ?1
+           AND timestamp >= datetime(?2, 'unixepoch')";
+    let (count, first_at, last_at) = conn.query_row(
+        &format!("SELECT COUNT(*), MIN(timestamp), MAX(timestamp) {}", filter),
+        params![round_id as i64, since],
+        |row| {
+            Ok((
+                row.get::<_, i64>(0)? as u64,
+                row.get::<_, Option<String>>(1)?,
+                row.get::<_, Option<String>>(2)?,
+            ))
+        },
+    )?;
+    let last = conn
+        .query_row(
+            &format!(
+                "SELECT error_message, error_name {} ORDER BY id DESC LIMIT 1",
+                filter
+            ),
+            params![round_id as i64, since],
+            |row| {
+                Ok((
+                    row.get::<_, Option<String>>(0)?,
+                    row.get::<_, Option<String>>(1)?,
+                ))
+            },
+        )
+        .optional()?;
+    let (last_error, last_error_name) = last.unwrap_or_default();
+
+    Ok(SettleFailures {
+        count,
+        first_at,
+        last_at,
+        last_error,
+        last_error_name,
+    })
+}
+
+/// A round the keeper decided to cancel, and how the cancellation went
+#[derive(Clone, Debug)]
+pub struct RoundCancellation {
+    pub round_id: u64,
+    pub reason: String,
+    pub end_time: i64,
+    pub overdue_secs: i64,
+    pub failures: SettleFailures,
+    pub status: String, // cancelled|failed
+    pub transaction_signature: Option<String>,
+    pub error_message: Option<String>,
+}
+
+/// Write a `round_cancellations` audit row right away (not through the batching logger)
+pub fn record_round_cancellation(path: &str, entry: &RoundCancellation) -> SqlResult<()> {
+    let conn = open_state_db(path)?;
+    create_cancellation_table(&conn)?;
+    let instance_id = DEFAULT_INSTANCE_ID.get().cloned().unwrap_or_default();
+    conn.execute(
+        "INSERT INTO round_cancellations (
+            keeper_instance_id, round_id, reason, end_time, overdue_secs, failures,
+            first_failure_at, last_failure_at, last_error, last_error_name,
+            status, transaction_signature, error_message
+        ) VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13)",
+        params![
+            instance_id,
+            entry.round_id as i64,
+            entry.reason,
+            entry.end_time,
+            entry.overdue_secs,
+            entry.failures.count as i64,
+            entry.failures.first_at,
+            entry.failures.last_at,
+            entry.failures.last_error,
+            entry.failures.last_error_name,
+            entry.status,
+            entry.transaction_signature,
+            entry.error_message,
+        ],
+    )?;
+
+    Ok(())
+}
+
+/// Record the program status a keeper observed in `program_pauses`
+///
+/// Closes this keeper's open pause when the status moved away from it, and opens a new one
+/// when the program is paused (`status` other than `Active`). A pause still open from before
+/// a restart is carried on. Returns the status and duration of the pause that was closed.
+pub fn record_program_status(
+    path: &str,
+    status: &str,
+    now: i64,
+) -> SqlResult<Option<(String, i64)>> {
+    let conn = open_state_db(path)?;
+    create_program_pause_table(&conn)?;
+    let instance_id = DEFAULT_INSTANCE_ID.get().cloned().unwrap_or_default();
+
+    let open = conn
+        .query_row(
+            "SELECT id, status, started_at FROM program_pauses
+             WHERE keeper_instance_id = ? AND ended_at IS NULL
+             ORDER BY id DESC LIMIT 1",
+            [&instance_id],
+            |row| {
+                Ok((
+                    row.get::<_, i64>(0)?,
+                    row.get::<_, String>(1)?,
+                    row.get::<_, i64>(2)?,
+                ))
+            },
+        )
+        .optional()?;
+
+    let mut closed = None;
+    if let Some((id, open_status, started_at)) = open {
+        if open_status == status {
+            return Ok(None);
+        }
+        let duration_secs = (now - started_at).max(0);
+        conn.execute(
+            "UPDATE program_pauses SET ended_at = ?1, duration_secs = ?2 WHERE id = ?3",
+            params![now, duration_secs, id],
+        )?;
+        closed = Some((open_status, duration_secs));
+    }
+
+    if status != "Active" {
+        conn.execute(
+            "INSERT INTO program_pauses (keeper_instance_id, status, started_at)
+             VALUES (?1, ?2, ?3)",
+            params![instance_id, status, now],
+        )?;
+    }
+
+    Ok(closed)
+}
+
+/// Key of the lowest round id not yet `Ended`
+const ROUND_WATERMARK_KEY: &str = "round_watermark";
+
+/// Lowest round id not yet `Ended`, as last stored by a keeper
+pub fn load_round_watermark(path: &str) -> SqlResult<Option<u64>> {
+    let conn = open_state_db(path)?;
+    let value = conn
+        .query_row(
+            "SELECT value FROM keeper_state WHERE key = ?",
+            [ROUND_WATERMARK_KEY],
+            |row| row.get::<_, i64>(0),
+        )
+        .optional()?;
+
+    Ok(value.map(|v| v as u64))
+}
+
+/// Store the round watermark; never moves it backwards
+pub fn store_round_watermark(path: &str, round_id: u64) -> SqlResult<()> {
+    let conn = open_state_db(path)?;
+    conn.execute(
+        "INSERT INTO keeper_state (key, value) VALUES (?1, ?2)
+         ON CONFLICT(key) DO UPDATE SET
+             value = MAX(value, excluded.value),
+             updated_at = CURRENT_TIMESTAMP",
+        params![ROUND_WATERMARK_KEY, round_id as i64],
+    )?;
+
+    Ok(())
+}
+
+/// Read-write connection for the keeper state, separate from the writer thread's
+fn open_state_db(path: &str) -> SqlResult<Connection> {
+    if let Some(parent) = Path::new(path).parent() {
+        let _ = create_dir_all(parent);
     }
+    let conn = Connection::open(path)?;
+    let _ = conn.pragma_update(None, "busy_timeout", 5000);
+    create_state_table(&conn)?;
+    Ok(conn)
 }
 
 pub fn is_initialized() -> bool {
@@ -180,7 +514,7 @@ pub fn set_default_instance_id(id: String) {
 
 #[derive(Clone, Debug, Default)]
 pub struct TxContext {
-    pub keeper_type: String, // start|settle
+    pub keeper_type: String, // start|settle|cancel|create|admin
     pub op: String,          // operation name
     pub round_id: Option<i64>,
     pub group_id: Option<i64>,
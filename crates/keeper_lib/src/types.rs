@@ -1,5 +1,7 @@
 pub mod asset_account;
+pub mod bet;
 pub mod config_account;
 pub mod enums;
 pub mod group_asset_account;
+pub mod price_update;
 pub mod round_account;
@@ -0,0 +1 @@
+pub type BetAccount = crate::idl::types::Bet;
@@ -1,21 +1 @@
-use anchor_lang::prelude::*;
-
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
-pub struct GroupAssetAccount {
-    pub id: u64,
-    pub round: Pubkey,
-    pub symbol: [u8; 8],
-    pub total_assets: u64,
-    pub total_final_price: u64,
-    pub total_growth_rate_bps: i64,
-    pub captured_start_price_assets: u64,
-    pub captured_end_price_assets: u64,
-    pub avg_growth_rate_bps: Option<i64>,
-    pub finalized_start_price_assets: u64,
-    pub finalized_end_price_assets: u64,
-    pub settled_assets: u64,
-    pub created_at: i64,
-    pub start_price_at: Option<i64>,
-    pub finalized_price_at: Option<i64>,
-    pub bump: u8,
-}
+pub type GroupAssetAccount = crate::idl::types::GroupAsset;
@@ -0,0 +1 @@
+pub type PriceUpdateAccount = crate::idl::types::PriceUpdateV2;
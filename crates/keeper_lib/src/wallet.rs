@@ -1,7 +1,170 @@
 use anyhow::{Context, Result, bail};
 use serde_json::from_str;
-use solana_sdk::{signature::Keypair, signer::SeedDerivable};
-use std::fs::read_to_string;
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::{Keypair, Signature},
+    signer::{SeedDerivable, Signer, SignerError},
+};
+use std::{env, fs::read_to_string, sync::Arc, time::Duration};
+
+pub mod local_server;
+pub mod remote;
+
+use remote::RemoteSigner;
+
+/// Signer used by the keepers to sign transactions
+///
+/// Every implementation is a regular Solana `Signer`, so it can be passed wherever a
+/// keypair was used before.
+pub trait KeeperSigner: Signer + Send + Sync {
+    /// Where the key lives (`file`, `env` or `remote`), for logs
+    fn kind(&self) -> &'static str;
+}
+
+/// Signers of a keeper transaction
+///
+/// The fee payer pays fees and rent; the authority is the key listed in
+/// `ConfigAccount::keeper_authorities` and signs the program instructions. Both can be the
+/// same signer.
+#[derive(Clone, Copy)]
+pub struct TxSigners<'a> {
+    pub fee_payer: &'a dyn KeeperSigner,
+    pub authority: &'a dyn KeeperSigner,
+}
+
+impl<'a> TxSigners<'a> {
+    pub fn new(fee_payer: &'a dyn KeeperSigner, authority: &'a dyn KeeperSigner) -> Self {
+        Self {
+            fee_payer,
+            authority,
+        }
+    }
+
+    /// One signer acting as both fee payer and authority
+    pub fn single(signer: &'a dyn KeeperSigner) -> Self {
+        Self::new(signer, signer)
+    }
+
+    pub fn fee_payer_pubkey(&self) -> Pubkey {
+        self.fee_payer.pubkey()
+    }
+
+    pub fn authority_pubkey(&self) -> Pubkey {
+        self.authority.pubkey()
+    }
+
+    /// Signers among these whose signature a message requires, given its signer keys
+    pub fn required(&self, signer_keys: &[Pubkey]) -> Vec<&'a dyn KeeperSigner> {
+        let mut signers = vec![self.fee_payer];
+        if self.authority_pubkey() != self.fee_payer_pubkey()
+            && signer_keys.contains(&self.authority_pubkey())
+        {
+            signers.push(self.authority);
+        }
+        signers
+    }
+}
+
+/// Where the keeper signer comes from
+#[derive(Clone, Debug)]
+pub enum SignerSource {
+    /// JSON keypair file
+    File(String),
+    /// Environment variable holding a base58 secret
+    Env(String),
+    /// Remote signing service
+    Remote {
+        url: String,
+        token: Option<String>,
+        timeout_ms: u64,
+    },
+}
+
+impl SignerSource {
+    pub fn load(&self) -> Result<Arc<dyn KeeperSigner>> {
+        Ok(match self {
+            Self::File(path) => Arc::new(FileSigner::load(path)?),
+            Self::Env(var) => Arc::new(EnvSigner::from_env(var)?),
+            Self::Remote {
+                url,
+                token,
+                timeout_ms,
+            } => Arc::new(RemoteSigner::connect(
+                url,
+                token.clone(),
+                Duration::from_millis(*timeout_ms),
+            )?),
+        })
+    }
+}
+
+/// Keypair loaded from a JSON keypair file
+pub struct FileSigner {
+    keypair: Keypair,
+}
+
+impl FileSigner {
+    pub fn load(path: &str) -> Result<Self> {
+        Ok(Self {
+            keypair: load_keypair_from_file(path)?,
+        })
+    }
+}
+
+/// Keypair from a base58 secret, usually held in an environment variable
+pub struct EnvSigner {
+    keypair: Keypair,
+}
+
+impl EnvSigner {
+    /// Load the base58 secret from the given environment variable
+    pub fn from_env(var: &str) -> Result<Self> {
+        let secret = env::var(var).with_context(|| format!("{} must be set", var))?;
+        Self::from_base58(secret.trim()).with_context(|| format!("Invalid secret in {}", var))
+    }
+
+    /// 64-byte keypair (or 32-byte seed) encoded in base58
+    pub fn from_base58(secret: &str) -> Result<Self> {
+        let bytes = bs58::decode(secret)
+            .into_vec()
+            .context("Secret is not valid base58")?;
+        let keypair = match bytes.len() {
+            64 => Keypair::try_from(bytes.as_slice())
+                .map_err(|e| anyhow::anyhow!("Invalid keypair bytes: {}", e))?,
+            32 => Keypair::from_seed(&bytes)
+                .map_err(|e| anyhow::anyhow!("Invalid keypair seed: {}", e))?,
+            n => bail!("Invalid secret length: expected 32 or 64 bytes, got {}", n),
+        };
+        Ok(Self { keypair })
+    }
+}
+
+macro_rules! impl_keypair_signer {
+    ($ty:ty, $kind:literal) => {
+        impl Signer for $ty {
+            fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+                self.keypair.try_pubkey()
+            }
+
+            fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+                self.keypair.try_sign_message(message)
+            }
+
+            fn is_interactive(&self) -> bool {
+                false
+            }
+        }
+
+        impl KeeperSigner for $ty {
+            fn kind(&self) -> &'static str {
+                $kind
+            }
+        }
+    };
+}
+
+impl_keypair_signer!(FileSigner, "file");
+impl_keypair_signer!(EnvSigner, "env");
 
 /// Load keypair from file
 pub fn load_keypair_from_file(path: &str) -> Result<Keypair> {
@@ -0,0 +1,268 @@
+//! Local stand-in for the remote signing service, for tests and devnet.
+//!
+//! Speaks the same protocol as [`RemoteSigner`](super::remote::RemoteSigner) over a
+//! minimal HTTP/1.1 server, one connection at a time.
+
+use anyhow::{Context, Result, bail};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+use solana_sdk::signature::{Keypair, Signer};
+use std::{
+    io::{BufRead, BufReader, Write},
+    net::{SocketAddr, TcpListener, TcpStream},
+    thread::{self, JoinHandle},
+};
+use tracing::{debug, warn};
+
+/// Max request body accepted (a transaction message is at most ~1.2KB)
+const MAX_BODY_SIZE: usize = 16 * 1024;
+
+/// Serve signatures for `keypair` on `addr` (use `127.0.0.1:0` for a free port)
+///
+/// Returns the bound address and the server thread, which runs until the process exits.
+pub fn spawn_local_signer(
+    keypair: Keypair,
+    addr: &str,
+    token: Option<String>,
+) -> Result<(SocketAddr, JoinHandle<()>)> {
+    let listener = TcpListener::bind(addr)
+        .with_context(|| format!("Failed to bind local signer: {}", addr))?;
+    let local_addr = listener.local_addr()?;
+
+    let handle = thread::spawn(move || {
+        for stream in listener.incoming() {
+            let stream = match stream {
+                Ok(stream) => stream,
+                Err(err) => {
+                    warn!(error = %err, "local signer accept failed");
+                    continue;
+                }
+            };
+            if let Err(err) = handle_connection(stream, &keypair, token.as_deref()) {
+                warn!(error = %err, "local signer request failed");
+            }
+        }
+    });
+
+    Ok((local_addr, handle))
+}
+
+struct Request {
+    method: String,
+    path: String,
+    authorization: Option<String>,
+    body: Vec<u8>,
+}
+
+fn handle_connection(stream: TcpStream, keypair: &Keypair, token: Option<&str>) -> Result<()> {
+    let mut reader = BufReader::new(stream.try_clone()?);
+    let mut stream = stream;
+
+    let req = match read_request(&mut reader) {
+        Ok(req) => req,
+        Err(err) => {
+            return write_response(&mut stream, 400, &json!({ "error": format!("{:#}", err) }));
+        }
+    };
+    debug!(method = %req.method, path = %req.path, "local signer request");
+
+    if let Some(token) = token {
+        let expected = format!("Bearer {}", token);
+        if req.authorization.as_deref() != Some(expected.as_str()) {
+            return write_response(&mut stream, 401, &json!({ "error": "unauthorized" }));
+        }
+    }
+
+    match (req.method.as_str(), req.path.as_str()) {
+        ("GET", "/v1/pubkey") => write_response(
+            &mut stream,
+            200,
+            &json!({ "pubkey": keypair.pubkey().to_string() }),
+        ),
+        ("POST", "/v1/sign") => match sign(keypair, &req.body) {
+            Ok(sig) => write_response(&mut stream, 200, &json!({ "signature": sig })),
+            Err(err) => write_response(&mut stream, 400, &json!({ "error": format!("{:#}", err) })),
+        },
+        _ => write_response(&mut stream, 404, &json!({ "error": "not found" })),
+    }
+}
+
+fn sign(keypair: &Keypair, body: &[u8]) -> Result<String> {
+    let body: Value = serde_json::from_slice(body).context("Invalid JSON body")?;
+    if let Some(pubkey) = body["pubkey"].as_str()
+        && pubkey != keypair.pubkey().to_string()
+    {
+        bail!("Unknown pubkey: {}", pubkey);
+    }
+    let message = body["message"].as_str().context("Missing message")?;
+    let message = STANDARD
+        .decode(message)
+        .context("Message is not valid base64")?;
+    Ok(keypair.sign_message(&message).to_string())
+}
+
+fn read_request(reader: &mut impl BufRead) -> Result<Request> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut parts = line.split_whitespace();
+    let method = parts.next().context("Missing method")?.to_string();
+    let path = parts.next().context("Missing path")?.to_string();
+
+    let mut content_length = 0usize;
+    let mut authorization = None;
+    loop {
+        let mut header = String::new();
+        if reader.read_line(&mut header)? == 0 {
+            break;
+        }
+        let header = header.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        let Some((name, value)) = header.split_once(':') else {
+            continue;
+        };
+        let value = value.trim();
+        if name.eq_ignore_ascii_case("content-length") {
+            content_length = value.parse().context("Invalid Content-Length")?;
+        } else if name.eq_ignore_ascii_case("authorization") {
+            authorization = Some(value.to_string());
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        bail!("Body too large: {} bytes", content_length);
+    }
+    let mut body = vec![0u8; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        authorization,
+        body,
+    })
+}
+
+fn write_response(stream: &mut TcpStream, status: u16, body: &Value) -> Result<()> {
+    let reason = match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        _ => "Not Found",
+    };
+    let body = body.to_string();
+    write!(
+        stream,
+        "HTTP/1.1 {} {}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        status,
+        reason,
+        body.len(),
+        body
+    )?;
+    stream.flush()?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::wallet::remote::RemoteSigner;
+    use solana_sdk::{pubkey::Pubkey, signature::Signature};
+    use std::time::Duration;
+
+    const TIMEOUT: Duration = Duration::from_secs(5);
+    const MESSAGE: &[u8] = b"gold rush";
+
+    fn connect(addr: SocketAddr, token: Option<&str>) -> Result<RemoteSigner> {
+        RemoteSigner::connect(
+            &format!("http://{}", addr),
+            token.map(str::to_string),
+            TIMEOUT,
+        )
+    }
+
+    /// Signing service that reports `pubkey` and answers sign requests with `sign(message)`
+    fn spawn_fake_signer(
+        pubkey: Pubkey,
+        sign: impl Fn(&[u8]) -> Signature + Send + 'static,
+    ) -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let addr = listener.local_addr().unwrap();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let mut reader = BufReader::new(stream.try_clone().unwrap());
+                let mut stream = stream;
+                let req = read_request(&mut reader).unwrap();
+                let body = match req.path.as_str() {
+                    "/v1/pubkey" => json!({ "pubkey": pubkey.to_string() }),
+                    _ => {
+                        let body: Value = serde_json::from_slice(&req.body).unwrap();
+                        let message = STANDARD.decode(body["message"].as_str().unwrap()).unwrap();
+                        json!({ "signature": sign(&message).to_string() })
+                    }
+                };
+                write_response(&mut stream, 200, &body).unwrap();
+            }
+        });
+        addr
+    }
+
+    #[test]
+    fn remote_signer_round_trip() {
+        let keypair = Keypair::new();
+        let pubkey = keypair.pubkey();
+        let (addr, _) =
+            spawn_local_signer(keypair, "127.0.0.1:0", Some("secret".to_string())).unwrap();
+
+        let signer = connect(addr, Some("secret")).unwrap();
+        assert_eq!(signer.pubkey(), pubkey);
+        let signature = signer.try_sign_message(MESSAGE).unwrap();
+        assert!(signature.verify(pubkey.as_ref(), MESSAGE));
+    }
+
+    #[test]
+    fn remote_signer_needs_the_token() {
+        let (addr, _) =
+            spawn_local_signer(Keypair::new(), "127.0.0.1:0", Some("secret".to_string())).unwrap();
+
+        assert!(connect(addr, None).is_err());
+        assert!(connect(addr, Some("wrong")).is_err());
+    }
+
+    #[test]
+    fn local_signer_rejects_unknown_pubkey() {
+        let keypair = Keypair::new();
+        let body = json!({
+            "pubkey": Pubkey::new_unique().to_string(),
+            "message": STANDARD.encode(MESSAGE),
+        });
+
+        let err = sign(&keypair, body.to_string().as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("Unknown pubkey"));
+    }
+
+    #[test]
+    fn remote_signer_rejects_signature_by_another_key() {
+        let other = Keypair::new();
+        let addr = spawn_fake_signer(Keypair::new().pubkey(), move |message| {
+            other.sign_message(message)
+        });
+
+        let signer = connect(addr, None).unwrap();
+        let err = signer.try_sign_message(MESSAGE).unwrap_err();
+        assert!(err.to_string().contains("not made by"));
+    }
+
+    #[test]
+    fn remote_signer_rejects_tampered_signature() {
+        let keypair = Keypair::new();
+        let addr = spawn_fake_signer(keypair.pubkey(), move |_| {
+            keypair.sign_message(b"another message")
+        });
+
+        let signer = connect(addr, None).unwrap();
+        let err = signer.try_sign_message(MESSAGE).unwrap_err();
+        assert!(err.to_string().contains("not made by"));
+    }
+}
@@ -0,0 +1,106 @@
+//! Client for a remote signing service, so the keeper host never holds the authority key.
+//!
+//! Protocol (JSON over HTTP, optional `Authorization: Bearer <token>`):
+//!
+//! * `GET  {url}/v1/pubkey` -> `{"pubkey": "<base58>"}`
+//! * `POST {url}/v1/sign` with `{"pubkey": "<base58>", "message": "<base64>"}`
+//!   -> `{"signature": "<base58>"}`
+
+use anyhow::{Context, Result, anyhow};
+use base64::{Engine, engine::general_purpose::STANDARD};
+use serde_json::{Value, json};
+use solana_sdk::{
+    pubkey::Pubkey,
+    signature::Signature,
+    signer::{Signer, SignerError},
+};
+use std::{str::FromStr, time::Duration};
+
+use super::KeeperSigner;
+
+/// Signer that forwards every message to a remote signing service
+pub struct RemoteSigner {
+    agent: ureq::Agent,
+    url: String,
+    token: Option<String>,
+    pubkey: Pubkey,
+}
+
+impl RemoteSigner {
+    /// Connect to the service and fetch the public key it signs for
+    pub fn connect(url: &str, token: Option<String>, timeout: Duration) -> Result<Self> {
+        let agent = ureq::AgentBuilder::new().timeout(timeout).build();
+        let url = url.trim_end_matches('/').to_string();
+
+        let mut req = agent.get(&format!("{}/v1/pubkey", url));
+        if let Some(token) = &token {
+            req = req.set("Authorization", &format!("Bearer {}", token));
+        }
+        let body: Value = req
+            .call()
+            .context("Failed to reach remote signer")?
+            .into_json()
+            .context("Invalid remote signer pubkey response")?;
+        let pubkey = body["pubkey"]
+            .as_str()
+            .and_then(|s| Pubkey::from_str(s).ok())
+            .context("Remote signer returned no valid pubkey")?;
+
+        Ok(Self {
+            agent,
+            url,
+            token,
+            pubkey,
+        })
+    }
+
+    fn sign(&self, message: &[u8]) -> Result<Signature> {
+        let mut req = self.agent.post(&format!("{}/v1/sign", self.url));
+        if let Some(token) = &self.token {
+            req = req.set("Authorization", &format!("Bearer {}", token));
+        }
+        let body: Value = req
+            .send_json(json!({
+                "pubkey": self.pubkey.to_string(),
+                "message": STANDARD.encode(message),
+            }))
+            .context("Remote sign request failed")?
+            .into_json()
+            .context("Invalid remote sign response")?;
+        let signature = body["signature"]
+            .as_str()
+            .and_then(|s| Signature::from_str(s).ok())
+            .context("Remote signer returned no valid signature")?;
+
+        // Never put a signature on the wire that doesn't match the expected key
+        if !signature.verify(self.pubkey.as_ref(), message) {
+            return Err(anyhow!(
+                "Remote signer returned a signature not made by {}",
+                self.pubkey
+            ));
+        }
+
+        Ok(signature)
+    }
+}
+
+impl Signer for RemoteSigner {
+    fn try_pubkey(&self) -> Result<Pubkey, SignerError> {
+        Ok(self.pubkey)
+    }
+
+    fn try_sign_message(&self, message: &[u8]) -> Result<Signature, SignerError> {
+        self.sign(message)
+            .map_err(|e| SignerError::Custom(format!("{:#}", e)))
+    }
+
+    fn is_interactive(&self) -> bool {
+        false
+    }
+}
+
+impl KeeperSigner for RemoteSigner {
+    fn kind(&self) -> &'static str {
+        "remote"
+    }
+}
@@ -0,0 +1,485 @@
+use anyhow::{Context, Result, anyhow, bail};
+use keeper_lib::{
+    client::{
+        anchor::{
+            emergency_pause_instruction, emergency_unpause_instruction, program_pause_instruction,
+            program_unpause_instruction, send_admin_tx, update_config_instruction,
+        },
+        export::{
+            ExportFormat, decode_signed_transaction, export_instruction, submit_signed_transaction,
+        },
+    },
+    idl::instructions::update_config::Args as UpdateConfigArgs,
+    pda::derive_config_pda,
+    types::{config_account::ConfigAccount, enums::ProgramStatus},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::{
+    fmt::Display,
+    fs,
+    io::{self, BufRead, Write},
+    str::FromStr,
+};
+use tracing::info;
+
+use crate::App;
+
+pub const USAGE: &str = "\
+usage: gold-rush-admin <command> [options]
+
+commands:
+  config                 print the current config
+  pause                  pause the program
+  unpause                unpause a paused program
+  emergency-pause        put the program in emergency pause
+  emergency-unpause      lift an emergency pause
+  update-config          update the config fields given as options
+  submit <file>          broadcast a transaction exported and signed offline
+
+options:
+  --export <file>        write the unsigned transaction to <file> instead of sending it
+  --format <format>      export format: base64 (default), base58, or json (raw instruction)
+  --nonce-account <pubkey>
+                         durable nonce for the exported transaction, so it doesn't expire
+                         before it is signed offline (base64/base58 only)
+
+update-config options:
+  --admin <pubkey>
+  --keeper-authorities <pubkey,...>
+  --token-mint <pubkey>
+  --treasury <pubkey>
+  --single-asset-feed-id <hex>
+  --max-price-update-age-secs <secs>
+  --fee-single-asset-bps <bps>
+  --fee-group-battle-bps <bps>
+  --min-bet-amount <amount>
+  --bet-cutoff-window-secs <secs>
+  -y, --yes              send without asking for confirmation";
+
+pub enum AdminCommand {
+    ShowConfig,
+    Pause,
+    Unpause,
+    EmergencyPause,
+    EmergencyUnpause,
+    UpdateConfig(Box<UpdateConfigArgs>),
+    /// Broadcast the signed transaction in the file
+    Submit(String),
+}
+
+pub struct AdminArgs {
+    pub command: AdminCommand,
+    /// Skip the update-config confirmation
+    pub yes: bool,
+    /// Write the unsigned transaction here instead of signing and sending it
+    pub export: Option<String>,
+    pub format: ExportFormat,
+    /// Durable nonce account the exported transaction advances instead of a recent blockhash
+    pub nonce_account: Option<Pubkey>,
+}
+
+impl AdminArgs {
+    /// Parse the command line, without the program name
+    pub fn parse(args: &[String]) -> Result<Self> {
+        let mut args = args.iter();
+        let command = args.next().context(USAGE)?;
+        let submit_path = match command.as_str() {
+            "submit" => Some(args.next().context("submit needs a transaction file")?),
+            _ => None,
+        };
+
+        let mut update = empty_update();
+        let mut yes = false;
+        let mut export = None;
+        let mut format = None;
+        let mut nonce_account = None;
+        while let Some(flag) = args.next() {
+            if flag == "-y" || flag == "--yes" {
+                yes = true;
+                continue;
+            }
+            let value = args
+                .next()
+                .with_context(|| format!("{} needs a value", flag))?;
+            match flag.as_str() {
+                "--export" => export = Some(value.clone()),
+                "--format" => format = Some(value.parse()?),
+                "--nonce-account" => nonce_account = Some(parse(flag, value)?),
+                "--admin" => update.new_admin = Some(parse(flag, value)?),
+                "--keeper-authorities" => {
+                    update.new_keeper_authorities = Some(
+                        value
+                            .split(',')
+                            .map(|v| parse(flag, v.trim()))
+                            .collect::<Result<_>>()?,
+                    )
+                }
+                "--token-mint" => update.new_token_mint = Some(parse(flag, value)?),
+                "--treasury" => update.new_treasury = Some(parse(flag, value)?),
+                "--single-asset-feed-id" => {
+                    update.new_single_asset_feed_id = Some(parse_feed_id(value)?)
+                }
+                "--max-price-update-age-secs" => {
+                    update.new_max_price_update_age_secs = Some(parse(flag, value)?)
+                }
+                "--fee-single-asset-bps" => {
+                    update.new_fee_single_asset_bps = Some(parse(flag, value)?)
+                }
+                "--fee-group-battle-bps" => {
+                    update.new_fee_group_battle_bps = Some(parse(flag, value)?)
+                }
+                "--min-bet-amount" => update.new_min_bet_amount = Some(parse(flag, value)?),
+                "--bet-cutoff-window-secs" => {
+                    update.new_bet_cutoff_window_secs = Some(parse(flag, value)?)
+                }
+                other => bail!("unknown option {}\n\n{}", other, USAGE),
+            }
+        }
+
+        let command = match command.as_str() {
+            "update-config" => AdminCommand::UpdateConfig(Box::new(update)),
+            _ if update != empty_update() => {
+                bail!("config options are only used by update-config\n\n{}", USAGE)
+            }
+            "config" => AdminCommand::ShowConfig,
+            "pause" => AdminCommand::Pause,
+            "unpause" => AdminCommand::Unpause,
+            "emergency-pause" => AdminCommand::EmergencyPause,
+            "emergency-unpause" => AdminCommand::EmergencyUnpause,
+            "submit" => AdminCommand::Submit(submit_path.cloned().unwrap_or_default()),
+            other => bail!("unknown command {}\n\n{}", other, USAGE),
+        };
+        if format.is_some() && export.is_none() {
+            bail!("--format is only used with --export");
+        }
+        if nonce_account.is_some() && export.is_none() {
+            bail!("--nonce-account is only used with --export");
+        }
+        if nonce_account.is_some() && format == Some(ExportFormat::Json) {
+            bail!("--nonce-account is only used by the transaction formats, not json");
+        }
+        if export.is_some() && matches!(command, AdminCommand::ShowConfig | AdminCommand::Submit(_))
+        {
+            bail!("--export is only used by the instruction commands");
+        }
+
+        Ok(Self {
+            command,
+            yes,
+            export,
+            format: format.unwrap_or(ExportFormat::Base64),
+            nonce_account,
+        })
+    }
+}
+
+/// Run an admin command; `None` when nothing was sent
+///
+/// With `--export` the instruction is built against the live config, with its admin as
+/// signer and fee payer, and written to the file unsigned.
+pub fn run(app: &App, args: AdminArgs) -> Result<Option<Signature>> {
+    if let AdminCommand::Submit(path) = &args.command {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read transaction file: {}", path))?;
+        let tx = decode_signed_transaction(&raw)
+            .with_context(|| format!("Invalid transaction file: {}", path))?;
+        let sig = submit_signed_transaction(&app.rpc, &tx)?;
+        info!(path = %path, tx_sig = %sig, "signed transaction submitted");
+        return Ok(Some(sig));
+    }
+
+    let cfg = app.fetch_config()?;
+    let config_pda = derive_config_pda(&app.program_id);
+    let admin = Pubkey::new_from_array(cfg.admin.to_bytes());
+
+    let (op, instruction) = match args.command {
+        AdminCommand::ShowConfig => {
+            for (field, value) in config_fields(&cfg) {
+                println!("{:<28} {}", field, value);
+            }
+            return Ok(None);
+        }
+        AdminCommand::Submit(_) => unreachable!(),
+        AdminCommand::Pause => {
+            if !matches!(cfg.status, ProgramStatus::Active) {
+                bail!(
+                    "Program is {:?}, only an active program can be paused",
+                    cfg.status
+                );
+            }
+            (
+                "program_pause",
+                program_pause_instruction(&admin, &config_pda, &app.program_id),
+            )
+        }
+        AdminCommand::Unpause => {
+            if !matches!(cfg.status, ProgramStatus::Paused) {
+                bail!("Program is {:?}, not paused", cfg.status);
+            }
+            (
+                "program_unpause",
+                program_unpause_instruction(&admin, &config_pda, &app.program_id),
+            )
+        }
+        AdminCommand::EmergencyPause => {
+            if matches!(cfg.status, ProgramStatus::EmergencyPaused) {
+                bail!("Program is already in emergency pause");
+            }
+            (
+                "emergency_pause",
+                emergency_pause_instruction(&admin, &config_pda, &app.program_id),
+            )
+        }
+        AdminCommand::EmergencyUnpause => {
+            if !matches!(cfg.status, ProgramStatus::EmergencyPaused) {
+                bail!("Program is {:?}, not in emergency pause", cfg.status);
+            }
+            (
+                "emergency_unpause",
+                emergency_unpause_instruction(&admin, &config_pda, &app.program_id),
+            )
+        }
+        AdminCommand::UpdateConfig(mut update) => {
+            let changes = config_changes(&cfg, &mut update);
+            if changes.is_empty() {
+                bail!("No config changes");
+            }
+            println!("updateConfig:");
+            for change in &changes {
+                println!(
+                    "  {:<28} {} -> {}",
+                    change.field, change.current, change.proposed
+                );
+            }
+            // The multisig proposal is the review of an exported update
+            if args.export.is_none() && !args.yes && !confirm("Send updateConfig?")? {
+                println!("aborted");
+                return Ok(None);
+            }
+            (
+                "update_config",
+                update_config_instruction(&admin, &config_pda, &update, &app.program_id),
+            )
+        }
+    };
+
+    if let Some(path) = &args.export {
+        let out = export_instruction(
+            &app.rpc,
+            &admin,
+            &instruction,
+            args.format,
+            args.nonce_account.as_ref(),
+        )?;
+        fs::write(path, out).with_context(|| format!("Failed to write {}", path))?;
+        info!(op, path = %path, format = ?args.format, nonce_account = ?args.nonce_account, admin = %admin, "exported unsigned admin transaction");
+        return Ok(None);
+    }
+
+    let signers = app.signers();
+    let signer = signers.authority_pubkey();
+    if signer != admin {
+        bail!(
+            "Signer {} is not the config admin {} (set ADMIN_KEYPAIR_PATH, or use --export)",
+            signer,
+            admin
+        );
+    }
+    let sig = send_admin_tx(&app.rpc, &signers, op, instruction)?;
+
+    info!(op, tx_sig = %sig, "admin transaction sent");
+    Ok(Some(sig))
+}
+
+/// A config field `updateConfig` would change
+struct FieldChange {
+    field: &'static str,
+    current: String,
+    proposed: String,
+}
+
+/// Field-by-field diff of the update against the config
+///
+/// Fields set to their current value are dropped from the update, the program rejects
+/// some of them (e.g. the same admin).
+fn config_changes(cfg: &ConfigAccount, update: &mut UpdateConfigArgs) -> Vec<FieldChange> {
+    let mut changes = Vec::new();
+    diff_field(
+        &mut changes,
+        "admin",
+        &cfg.admin,
+        &mut update.new_admin,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "keeper_authorities",
+        &cfg.keeper_authorities,
+        &mut update.new_keeper_authorities,
+        |v| format_pubkeys(v),
+    );
+    diff_field(
+        &mut changes,
+        "token_mint",
+        &cfg.token_mint,
+        &mut update.new_token_mint,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "treasury",
+        &cfg.treasury,
+        &mut update.new_treasury,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "single_asset_feed_id",
+        &cfg.single_asset_feed_id,
+        &mut update.new_single_asset_feed_id,
+        |v| format!("0x{}", hex::encode(v)),
+    );
+    diff_field(
+        &mut changes,
+        "max_price_update_age_secs",
+        &cfg.max_price_update_age_secs,
+        &mut update.new_max_price_update_age_secs,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "fee_single_asset_bps",
+        &cfg.fee_single_asset_bps,
+        &mut update.new_fee_single_asset_bps,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "fee_group_battle_bps",
+        &cfg.fee_group_battle_bps,
+        &mut update.new_fee_group_battle_bps,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "min_bet_amount",
+        &cfg.min_bet_amount,
+        &mut update.new_min_bet_amount,
+        |v| v.to_string(),
+    );
+    diff_field(
+        &mut changes,
+        "bet_cutoff_window_secs",
+        &cfg.bet_cutoff_window_secs,
+        &mut update.new_bet_cutoff_window_secs,
+        |v| v.to_string(),
+    );
+    changes
+}
+
+fn diff_field<T: PartialEq>(
+    changes: &mut Vec<FieldChange>,
+    field: &'static str,
+    current: &T,
+    proposed: &mut Option<T>,
+    show: impl Fn(&T) -> String,
+) {
+    let Some(value) = proposed.as_ref() else {
+        return;
+    };
+    if value == current {
+        println!("  {:<28} unchanged ({}), skipped", field, show(current));
+        *proposed = None;
+        return;
+    }
+    changes.push(FieldChange {
+        field,
+        current: show(current),
+        proposed: show(value),
+    });
+}
+
+fn config_fields(cfg: &ConfigAccount) -> Vec<(&'static str, String)> {
+    vec![
+        ("admin", cfg.admin.to_string()),
+        (
+            "keeper_authorities",
+            format_pubkeys(&cfg.keeper_authorities),
+        ),
+        ("token_mint", cfg.token_mint.to_string()),
+        ("treasury", cfg.treasury.to_string()),
+        (
+            "single_asset_feed_id",
+            format!("0x{}", hex::encode(cfg.single_asset_feed_id)),
+        ),
+        (
+            "max_price_update_age_secs",
+            cfg.max_price_update_age_secs.to_string(),
+        ),
+        ("fee_single_asset_bps", cfg.fee_single_asset_bps.to_string()),
+        ("fee_group_battle_bps", cfg.fee_group_battle_bps.to_string()),
+        ("min_bet_amount", cfg.min_bet_amount.to_string()),
+        (
+            "bet_cutoff_window_secs",
+            cfg.bet_cutoff_window_secs.to_string(),
+        ),
+        ("min_time_factor_bps", cfg.min_time_factor_bps.to_string()),
+        ("max_time_factor_bps", cfg.max_time_factor_bps.to_string()),
+        (
+            "default_direction_factor_bps",
+            cfg.default_direction_factor_bps.to_string(),
+        ),
+        ("status", format!("{:?}", cfg.status)),
+        (
+            "current_round_counter",
+            cfg.current_round_counter.to_string(),
+        ),
+        ("version", cfg.version.to_string()),
+    ]
+}
+
+fn format_pubkeys<T: Display>(keys: &[T]) -> String {
+    let keys: Vec<String> = keys.iter().map(|k| k.to_string()).collect();
+    format!("[{}]", keys.join(", "))
+}
+
+fn empty_update() -> UpdateConfigArgs {
+    UpdateConfigArgs {
+        new_admin: None,
+        new_keeper_authorities: None,
+        new_token_mint: None,
+        new_treasury: None,
+        new_single_asset_feed_id: None,
+        new_max_price_update_age_secs: None,
+        new_fee_single_asset_bps: None,
+        new_fee_group_battle_bps: None,
+        new_min_bet_amount: None,
+        new_bet_cutoff_window_secs: None,
+    }
+}
+
+fn parse<T>(flag: &str, value: &str) -> Result<T>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    value
+        .parse()
+        .map_err(|e| anyhow!("invalid {} {}: {}", flag, value, e))
+}
+
+fn parse_feed_id(value: &str) -> Result<[u8; 32]> {
+    let bytes = hex::decode(value.strip_prefix("0x").unwrap_or(value))
+        .context("invalid --single-asset-feed-id")?;
+    bytes
+        .try_into()
+        .map_err(|_| anyhow!("--single-asset-feed-id must be 32 bytes"))
+}
+
+fn confirm(prompt: &str) -> Result<bool> {
+    print!("{} [y/N] ", prompt);
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    io::stdin().lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_lowercase().as_str(), "y" | "yes"))
+}
@@ -0,0 +1,89 @@
+use anyhow::Result;
+use keeper_lib::{
+    storage::sqlite::{
+        RoundCancellation, SettleFailures, record_round_cancellation, settle_failures_since,
+    },
+    types::round_account::RoundAccount,
+};
+use solana_sdk::signature::Signature;
+use tracing::{error, warn};
+
+const REASON: &str = "settle_failures_past_grace";
+
+/// When a round that can't be settled gets cancelled instead
+#[derive(Clone, Debug)]
+pub struct AutoCancelConfig {
+    pub enabled: bool,
+    /// Time past `end_time` before a failing round may be cancelled
+    pub grace_secs: u64,
+    /// Failed (or stale-feed skipped) settle attempts since `end_time` required to cancel
+    pub min_failures: u64,
+}
+
+/// Cancels rounds stuck past their grace window so bettors can withdraw
+///
+/// The failure history comes from the SQLite transaction logs, so the policy is off when
+/// logs are not persisted.
+pub struct AutoCancelPolicy {
+    cfg: AutoCancelConfig,
+    log_db_path: Option<String>,
+}
+
+impl AutoCancelPolicy {
+    pub fn new(cfg: AutoCancelConfig, log_db_path: Option<String>) -> Self {
+        if cfg.enabled && log_db_path.is_none() {
+            warn!("auto-cancel needs PERSIST_LOGS=true for the failure history, disabled");
+        }
+        Self { cfg, log_db_path }
+    }
+
+    /// Failure evidence if the round should be cancelled instead of settled
+    pub fn should_cancel(&self, round: &RoundAccount, now: i64) -> Option<SettleFailures> {
+        if !self.cfg.enabled {
+            return None;
+        }
+        let path = self.log_db_path.as_deref()?;
+        if now < round.end_time.saturating_add(self.cfg.grace_secs as i64) {
+            return None;
+        }
+
+        match settle_failures_since(path, round.id, round.end_time) {
+            Ok(failures) if failures.count >= self.cfg.min_failures => Some(failures),
+            Ok(_) => None,
+            Err(err) => {
+                warn!(round_id = round.id, error = %err, "settle failure history unavailable");
+                None
+            }
+        }
+    }
+
+    /// Record the decision, its evidence and the outcome in the audit table
+    pub fn record(
+        &self,
+        round: &RoundAccount,
+        now: i64,
+        failures: SettleFailures,
+        outcome: &Result<Signature>,
+    ) {
+        let Some(path) = self.log_db_path.as_deref() else {
+            return;
+        };
+        let (status, transaction_signature, error_message) = match outcome {
+            Ok(sig) => ("cancelled", Some(sig.to_string()), None),
+            Err(err) => ("failed", None, Some(format!("{:#}", err))),
+        };
+        let entry = RoundCancellation {
+            round_id: round.id,
+            reason: REASON.to_string(),
+            end_time: round.end_time,
+            overdue_secs: now - round.end_time,
+            failures,
+            status: status.to_string(),
+            transaction_signature,
+            error_message,
+        };
+        if let Err(err) = record_round_cancellation(path, &entry) {
+            error!(round_id = round.id, error = %err, "failed to record round cancellation");
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use keeper_lib::{client::rpc::Rpc, storage::sqlite::fee_spend_since};
+use solana_sdk::pubkey::Pubkey;
+use tracing::{debug, error, info, warn};
+
+const LAMPORTS_PER_SOL: f64 = 1_000_000_000.0;
+
+/// Balance thresholds and runway projection settings
+#[derive(Clone, Debug)]
+pub struct BalanceThresholds {
+    pub warn_lamports: u64,
+    pub critical_lamports: u64,
+    pub warn_runway_hours: f64,
+    pub critical_runway_hours: f64,
+    /// Recent window the fee spend rate is averaged over
+    pub spend_window_secs: u64,
+    /// Stop sending while the balance is critical
+    pub halt_on_critical: bool,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum BalanceLevel {
+    Ok,
+    Warn,
+    Critical,
+}
+
+/// Checks the fee payer balance each tick and projects its runway from the fee spend
+/// recorded in SQLite
+pub struct BalanceWatcher {
+    thresholds: BalanceThresholds,
+    /// Log database to read the fee spend from, `None` when logs are not persisted
+    log_db_path: Option<String>,
+}
+
+impl BalanceWatcher {
+    pub fn new(thresholds: BalanceThresholds, log_db_path: Option<String>) -> Self {
+        Self {
+            thresholds,
+            log_db_path,
+        }
+    }
+
+    /// Check the balance and emit warn/critical events; false if sending should pause
+    ///
+    /// A failed check never pauses sending, the send itself will surface the problem.
+    pub async fn check(&self, rpc: &Rpc, fee_payer: &Pubkey) -> bool {
+        let balance = match rpc
+            .read_async("get_balance", |c| c.get_balance(fee_payer))
+            .await
+        {
+            Ok(balance) => balance,
+            Err(err) => {
+                warn!(fee_payer = %fee_payer, error = %err, "balance check failed");
+                return true;
+            }
+        };
+
+        let spend_per_hour = self.spend_per_hour().await;
+        let runway_hours = spend_per_hour
+            .filter(|rate| *rate > 0.0)
+            .map(|rate| balance as f64 / rate);
+        let level = self.level(balance, runway_hours);
+
+        let balance_sol = balance as f64 / LAMPORTS_PER_SOL;
+        let spend_sol_per_hour = spend_per_hour.map(|rate| rate / LAMPORTS_PER_SOL);
+        match level {
+            BalanceLevel::Ok => {
+                debug!(fee_payer = %fee_payer, balance_sol, spend_sol_per_hour, runway_hours, "fee payer balance");
+            }
+            BalanceLevel::Warn => {
+                warn!(event = "balance_low", fee_payer = %fee_payer, balance_sol, spend_sol_per_hour, runway_hours, "fee payer balance low");
+            }
+            BalanceLevel::Critical => {
+                error!(event = "balance_critical", fee_payer = %fee_payer, balance_sol, spend_sol_per_hour, runway_hours, halt = self.thresholds.halt_on_critical, "fee payer balance critical");
+            }
+        }
+
+        if level == BalanceLevel::Critical && self.thresholds.halt_on_critical {
+            info!(fee_payer = %fee_payer, "sending paused until the fee payer is funded");
+            return false;
+        }
+        true
+    }
+
+    /// Worst of the balance and runway levels
+    fn level(&self, balance: u64, runway_hours: Option<f64>) -> BalanceLevel {
+        let t = &self.thresholds;
+        let by_balance = if balance < t.critical_lamports {
+            BalanceLevel::Critical
+        } else if balance < t.warn_lamports {
+            BalanceLevel::Warn
+        } else {
+            BalanceLevel::Ok
+        };
+        let by_runway = match runway_hours {
+            Some(h) if h < t.critical_runway_hours => BalanceLevel::Critical,
+            Some(h) if h < t.warn_runway_hours => BalanceLevel::Warn,
+            _ => BalanceLevel::Ok,
+        };
+        by_balance.max(by_runway)
+    }
+
+    /// Average lamports spent on fees per hour over the window, `None` if unknown
+    async fn spend_per_hour(&self) -> Option<f64> {
+        let path = self.log_db_path.clone()?;
+        let window_secs = self.thresholds.spend_window_secs.max(1);
+        let spend = tokio::task::spawn_blocking(move || fee_spend_since(&path, window_secs)).await;
+        match spend {
+            Ok(Ok(spend)) => Some(spend.lamports as f64 * 3600.0 / window_secs as f64),
+            Ok(Err(err)) => {
+                debug!(error = %err, "fee spend unavailable");
+                None
+            }
+            Err(err) => {
+                debug!(error = %err, "fee spend query failed");
+                None
+            }
+        }
+    }
+}
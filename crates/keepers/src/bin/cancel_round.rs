@@ -0,0 +1,40 @@
+use anyhow::Result;
+use keepers::{App, config};
+use std::{sync::Arc, time::Duration};
+use tokio::time::{Instant, MissedTickBehavior, interval_at};
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cfg = config::load()?;
+    keepers::logging::init_tracing(&cfg);
+
+    let period = Duration::from_secs(cfg.cancel_round_period_in_secs);
+    let start = Instant::now();
+    let mut ticker = interval_at(start, period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let app = Arc::new(App::init_from(cfg)?);
+    keepers::preflight::run(&app).await?;
+    app.spawn_rpc_probe();
+
+    loop {
+        ticker.tick().await;
+
+        // Each tick runs on its own task so a slow round doesn't hold back new ones;
+        // rounds still in progress from an earlier tick are skipped
+        let app = Arc::clone(&app);
+        tokio::spawn(async move {
+            match keepers::keepers::cancel_round::run_one(app).await {
+                Ok(sigs) => {
+                    if !sigs.is_empty() {
+                        info!(cancelled_rounds = sigs.len(), "cancelled rounds");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "run_one error");
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,42 @@
+use anyhow::Result;
+use keepers::{App, config, schedule::Schedule};
+use std::{sync::Arc, time::Duration};
+use tokio::time::{Instant, MissedTickBehavior, interval_at};
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<()> {
+    let cfg = config::load()?;
+    keepers::logging::init_tracing(&cfg);
+
+    let schedule = Arc::new(Schedule::load(&cfg.round_schedule_path)?);
+    info!(path = %cfg.round_schedule_path, templates = schedule.templates.len(), "loaded round schedule");
+
+    let period = Duration::from_secs(cfg.create_round_period_in_secs);
+    let start = Instant::now();
+    let mut ticker = interval_at(start, period);
+    ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
+
+    let app = Arc::new(App::init_from(cfg)?);
+    keepers::preflight::run(&app).await?;
+    app.spawn_rpc_probe();
+
+    loop {
+        ticker.tick().await;
+
+        let app = Arc::clone(&app);
+        let schedule = Arc::clone(&schedule);
+        tokio::spawn(async move {
+            match keepers::keepers::create_round::run_one(app, schedule).await {
+                Ok(sigs) => {
+                    if !sigs.is_empty() {
+                        info!(created_rounds = sigs.len(), "created rounds");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "run_one error");
+                }
+            }
+        });
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Result;
+use keepers::{
+    App,
+    admin::{self, AdminArgs},
+    config,
+};
+use std::env;
+
+/// Operator CLI for the admin instructions (pause, emergency pause, config updates)
+///
+/// Signs with `ADMIN_*` when set, otherwise with the keeper signer.
+fn main() -> Result<()> {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let args = AdminArgs::parse(&args)?;
+
+    let mut cfg = config::load()?;
+    keepers::logging::init_tracing(&cfg);
+    if let Some(admin_signer) = cfg.admin_signer.clone() {
+        cfg.signer = admin_signer;
+    }
+
+    let app = App::init_from(cfg)?;
+    if let Some(sig) = admin::run(&app, args)? {
+        println!("{}", sig);
+    }
+    Ok(())
+}
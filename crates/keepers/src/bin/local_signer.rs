@@ -0,0 +1,25 @@
+use anyhow::{Context, Result};
+use keeper_lib::wallet::{load_keypair_from_file, local_server::spawn_local_signer};
+use solana_sdk::signer::Signer;
+use std::env;
+use tracing::info;
+
+/// Local stand-in for the remote signing service (`KEEPER_SIGNER=remote`), for tests and devnet
+fn main() -> Result<()> {
+    let _ = dotenvy::dotenv();
+    tracing_subscriber::fmt::init();
+
+    let keypair_path =
+        env::var("LOCAL_SIGNER_KEYPAIR_PATH").context("LOCAL_SIGNER_KEYPAIR_PATH must be set")?;
+    let addr = env::var("LOCAL_SIGNER_ADDR").unwrap_or_else(|_| "127.0.0.1:9090".to_string());
+    let token = env::var("REMOTE_SIGNER_TOKEN").ok();
+
+    let keypair = load_keypair_from_file(&keypair_path)?;
+    let pubkey = keypair.pubkey();
+    let (local_addr, handle) = spawn_local_signer(keypair, &addr, token)?;
+    info!(addr = %local_addr, pubkey = %pubkey, "local signer listening");
+
+    handle
+        .join()
+        .map_err(|_| anyhow::anyhow!("local signer thread panicked"))
+}
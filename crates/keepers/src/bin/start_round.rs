@@ -1,6 +1,6 @@
 use anyhow::Result;
 use keepers::{App, config};
-use std::time::Duration;
+use std::{sync::Arc, time::Duration};
 use tokio::time::{Instant, MissedTickBehavior, interval_at};
 use tracing::info;
 
@@ -14,20 +14,27 @@ async fn main() -> Result<()> {
     let mut ticker = interval_at(start, period);
     ticker.set_missed_tick_behavior(MissedTickBehavior::Delay);
 
-    let app = App::init_from(cfg)?;
+    let app = Arc::new(App::init_from(cfg)?);
+    keepers::preflight::run(&app).await?;
+    app.spawn_rpc_probe();
 
     loop {
         ticker.tick().await;
 
-        match keepers::keepers::start_round::run_one(&app) {
-            Ok(sigs) => {
-                if !sigs.is_empty() {
-                    info!(started_rounds = sigs.len(), "started rounds");
+        // Each tick runs on its own task so a slow round doesn't hold back new ones;
+        // rounds still in progress from an earlier tick are skipped
+        let app = Arc::clone(&app);
+        tokio::spawn(async move {
+            match keepers::keepers::start_round::run_one(app).await {
+                Ok(sigs) => {
+                    if !sigs.is_empty() {
+                        info!(started_rounds = sigs.len(), "started rounds");
+                    }
+                }
+                Err(e) => {
+                    tracing::error!(error = %e, "run_one error");
                 }
             }
-            Err(e) => {
-                tracing::error!(error = %e, "run_one error");
-            }
-        }
+        });
     }
 }
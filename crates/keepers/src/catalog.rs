@@ -0,0 +1,85 @@
+use anyhow::{Context, Result, bail};
+use serde::Deserialize;
+use std::{collections::HashSet, fs};
+
+/// Groups and assets of a group battle round, in insertion order
+#[derive(Clone, Debug, Deserialize)]
+pub struct AssetCatalog {
+    #[serde(rename = "group", default)]
+    pub groups: Vec<CatalogGroup>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CatalogGroup {
+    pub symbol: String,
+    #[serde(rename = "asset", default)]
+    pub assets: Vec<CatalogAsset>,
+}
+
+#[derive(Clone, Debug, Deserialize)]
+pub struct CatalogAsset {
+    pub symbol: String,
+    /// Pyth price feed id, hex with or without `0x`
+    pub feed_id: String,
+}
+
+impl AssetCatalog {
+    pub fn load(path: &str) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read asset catalog: {}", path))?;
+        let catalog: AssetCatalog =
+            toml::from_str(&raw).with_context(|| format!("Invalid asset catalog: {}", path))?;
+        catalog
+            .validate()
+            .with_context(|| format!("Invalid asset catalog: {}", path))?;
+
+        Ok(catalog)
+    }
+
+    fn validate(&self) -> Result<()> {
+        if self.groups.is_empty() {
+            bail!("catalog has no groups");
+        }
+
+        let mut group_symbols = HashSet::new();
+        for group in &self.groups {
+            symbol_bytes(&group.symbol)?;
+            if !group_symbols.insert(group.symbol.as_str()) {
+                bail!("duplicate group {}", group.symbol);
+            }
+            if group.assets.is_empty() {
+                bail!("group {} has no assets", group.symbol);
+            }
+
+            let mut asset_symbols = HashSet::new();
+            for asset in &group.assets {
+                symbol_bytes(&asset.symbol)?;
+                if !asset_symbols.insert(asset.symbol.as_str()) {
+                    bail!("duplicate asset {} in group {}", asset.symbol, group.symbol);
+                }
+                let feed_id = asset.feed_id.strip_prefix("0x").unwrap_or(&asset.feed_id);
+                match hex::decode(feed_id) {
+                    Ok(bytes) if bytes.len() == 32 => {}
+                    _ => bail!("asset {} feed_id must be 32 hex bytes", asset.symbol),
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+/// On-chain symbol: up to 8 ASCII bytes, zero padded
+pub fn symbol_bytes(symbol: &str) -> Result<[u8; 8]> {
+    if symbol.is_empty() || symbol.len() > 8 || !symbol.is_ascii() {
+        bail!("symbol {:?} must be 1 to 8 ASCII characters", symbol);
+    }
+    let mut out = [0u8; 8];
+    out[..symbol.len()].copy_from_slice(symbol.as_bytes());
+    Ok(out)
+}
+
+/// Display form of an on-chain symbol
+pub fn symbol_str(symbol: &[u8; 8]) -> String {
+    let len = symbol.iter().position(|b| *b == 0).unwrap_or(symbol.len());
+    String::from_utf8_lossy(&symbol[..len]).into_owned()
+}
@@ -1,32 +1,59 @@
-use anyhow::{Context, Result};
+use crate::auto_cancel::AutoCancelConfig;
+use crate::balance::BalanceThresholds;
+use crate::discovery::DiscoveryConfig;
+use anyhow::{Context, Result, bail};
+use keeper_lib::{
+    client::{fee::PriorityFeeStrategy, pool::RpcEndpoint, rpc::ComputeUnitLimit},
+    wallet::SignerSource,
+};
 use solana_commitment_config::CommitmentLevel;
 use solana_sdk::pubkey::Pubkey;
 use std::{env, str::FromStr};
 
+/// Pyth Solana Receiver program, owner of the push oracle price feed accounts
+const DEFAULT_PYTH_RECEIVER_PROGRAM_ID: &str = "rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ";
+
 #[derive(Clone)]
 pub struct RuntimeConfig {
-    pub solana_rpc_url: String,
+    pub rpc_endpoints: Vec<RpcEndpoint>,
+    pub rpc_probe_interval_secs: u64,
     pub commitment: CommitmentLevel,
     pub rpc_timeout_ms: u64,
     pub preflight: bool,
     pub tx_max_retries: usize,
-    pub cu_limit: u32,
-    pub cu_price_micro_lamports: u64,
+    pub compute_units: ComputeUnitLimit,
+    pub priority_fee: PriorityFeeStrategy,
     pub backoff_ms: u64,
 
-    pub keeper_keypair_path: String,
+    pub signer: SignerSource,
+    pub fee_payer: Option<SignerSource>,
+    /// Config admin for `gold-rush-admin`, the keeper signer when unset
+    pub admin_signer: Option<SignerSource>,
     pub treasury: Pubkey,
+    pub balance: BalanceThresholds,
+    pub discovery: DiscoveryConfig,
+    pub auto_cancel: AutoCancelConfig,
+    /// Keep the read-only pass (discovery, due-round logs) running while the program is paused
+    pub pause_monitor: bool,
 
     pub gold_price_feed_id: String,
     pub token_mint: Pubkey,
 
     pub start_round_period_in_secs: u64,
     pub settle_round_period_in_secs: u64,
+    pub cancel_round_period_in_secs: u64,
+    pub create_round_period_in_secs: u64,
+    pub round_schedule_path: String,
+    pub keeper_concurrency: usize,
     pub max_remaining_accounts: usize,
+    pub use_lookup_tables: bool,
+    pub lookup_table_max_remaining_accounts: usize,
 
     pub token_program_id: Pubkey,
     pub associated_token_program_id: Pubkey,
     pub push_oracle_program_id: Pubkey,
+    /// Owner of the price feed accounts (the push oracle only derives their PDAs)
+    pub pyth_receiver_program_id: Pubkey,
     pub system_program_id: Pubkey,
     pub program_id: Pubkey,
 
@@ -46,19 +73,28 @@ pub struct RuntimeConfig {
 pub fn load() -> Result<RuntimeConfig> {
     let _ = dotenvy::dotenv();
 
-    let solana_rpc_url = env_str("SOLANA_RPC_URL", None).context("SOLANA_RPC_URL must be set")?;
+    let rpc_endpoints = env_rpc_endpoints()?;
+    let rpc_probe_interval_secs = env_u64("RPC_PROBE_INTERVAL_SECS", Some(30)).unwrap();
     let commitment = env_commitment("COMMITMENT", None).context("COMMITMENT must be set")?;
     let rpc_timeout_ms = env_u64("RPC_TIMEOUT_MS", None).context("RPC_TIMEOUT_MS must be set")?;
     let tx_max_retries = env_usize("TX_MAX_RETRIES", None).context("TX_MAX_RETRIES must be set")?;
     let preflight = env_bool("PREFLIGHT", None).context("PREFLIGHT must be set")?;
     let cu_limit = env_u32("COMPUTE_UNIT_LIMIT", None).context("COMPUTE_UNIT_LIMIT must be set")?;
+    let compute_units = env_compute_units(cu_limit)?;
     let cu_price_micro_lamports = env_u64("PRIORITY_FEE_MICROLAMPORTS", None)
         .context("PRIORITY_FEE_MICROLAMPORTS must be set")?;
+    let priority_fee = env_priority_fee(cu_price_micro_lamports)?;
     let backoff_ms = env_u64("BACKOFF_MS", None).context("BACKOFF_MS must be set")?;
 
-    let keeper_keypair_path =
-        env_str("KEEPER_KEYPAIR_PATH", None).context("KEEPER_KEYPAIR_PATH must be set")?;
+    let signer =
+        env_signer("KEEPER", "REMOTE_SIGNER")?.context("KEEPER_KEYPAIR_PATH must be set")?;
+    let fee_payer = env_signer("FEE_PAYER", "FEE_PAYER_REMOTE_SIGNER")?;
+    let admin_signer = env_signer("ADMIN", "ADMIN_REMOTE_SIGNER")?;
     let treasury = env_pubkey("TREASURY", None).context("TREASURY must be set")?;
+    let balance = env_balance_thresholds();
+    let discovery = env_discovery();
+    let auto_cancel = env_auto_cancel();
+    let pause_monitor = env_bool("PAUSE_MONITOR", Some(false)).unwrap();
 
     let gold_price_feed_id =
         env_str("GOLD_PRICE_FEED_ID", None).context("GOLD_PRICE_FEED_ID must be set")?;
@@ -68,8 +104,16 @@ pub fn load() -> Result<RuntimeConfig> {
         .context("START_ROUND_PERIOD_IN_SECS must be set")?;
     let settle_round_period_in_secs = env_u64("SETTLE_ROUND_PERIOD_IN_SECS", None)
         .context("SETTLE_ROUND_PERIOD_IN_SECS must be set")?;
+    let cancel_round_period_in_secs = env_u64("CANCEL_ROUND_PERIOD_IN_SECS", Some(30)).unwrap();
+    let create_round_period_in_secs = env_u64("CREATE_ROUND_PERIOD_IN_SECS", Some(60)).unwrap();
+    let round_schedule_path =
+        env_str("ROUND_SCHEDULE_PATH", Some("schedule.toml".to_string())).unwrap();
+    let keeper_concurrency = env_usize("KEEPER_CONCURRENCY", Some(4)).unwrap();
     let max_remaining_accounts =
         env_usize("MAX_REMAINING_ACCOUNTS", None).context("MAX_REMAINING_ACCOUNTS must be set")?;
+    let use_lookup_tables = env_bool("USE_LOOKUP_TABLES", Some(false)).unwrap();
+    let lookup_table_max_remaining_accounts =
+        env_usize("LOOKUP_TABLE_MAX_REMAINING_ACCOUNTS", Some(48)).unwrap();
 
     let token_program_id =
         env_pubkey("TOKEN_PROGRAM_ID", None).context("TOKEN_PROGRAM_ID must be set")?;
@@ -77,6 +121,11 @@ pub fn load() -> Result<RuntimeConfig> {
         .context("ASSOCIATED_TOKEN_PROGRAM_ID must be set")?;
     let push_oracle_program_id =
         env_pubkey("PUSH_ORACLE_PROGRAM_ID", None).context("PUSH_ORACLE_PROGRAM_ID must be set")?;
+    let pyth_receiver_program_id = env_pubkey(
+        "PYTH_RECEIVER_PROGRAM_ID",
+        Pubkey::from_str(DEFAULT_PYTH_RECEIVER_PROGRAM_ID).ok(),
+    )
+    .unwrap();
     let system_program_id =
         env_pubkey("SYSTEM_PROGRAM_ID", None).context("SYSTEM_PROGRAM_ID must be set")?;
     let program_id = env_pubkey("PROGRAM_ID", None).context("PROGRAM_ID must be set")?;
@@ -94,24 +143,38 @@ pub fn load() -> Result<RuntimeConfig> {
     let keeper_instance_id = env_str("KEEPER_INSTANCE_ID", None);
 
     Ok(RuntimeConfig {
-        solana_rpc_url,
+        rpc_endpoints,
+        rpc_probe_interval_secs,
         commitment,
         rpc_timeout_ms,
         preflight,
         tx_max_retries,
-        cu_limit,
-        cu_price_micro_lamports,
+        compute_units,
+        priority_fee,
         backoff_ms,
-        keeper_keypair_path,
+        signer,
+        fee_payer,
+        admin_signer,
         treasury,
+        balance,
+        discovery,
+        auto_cancel,
+        pause_monitor,
         gold_price_feed_id,
         token_mint,
         start_round_period_in_secs,
         settle_round_period_in_secs,
+        cancel_round_period_in_secs,
+        create_round_period_in_secs,
+        round_schedule_path,
+        keeper_concurrency,
         max_remaining_accounts,
+        use_lookup_tables,
+        lookup_table_max_remaining_accounts,
         token_program_id,
         associated_token_program_id,
         push_oracle_program_id,
+        pyth_receiver_program_id,
         system_program_id,
         program_id,
         log_level,
@@ -165,6 +228,112 @@ fn env_commitment(key: &str, default: Option<CommitmentLevel>) -> Option<Commitm
     .or(default)
 }
 
+fn env_rpc_endpoints() -> Result<Vec<RpcEndpoint>> {
+    let list = env_str("SOLANA_RPC_URLS", None)
+        .or_else(|| env_str("SOLANA_RPC_URL", None))
+        .context("SOLANA_RPC_URL or SOLANA_RPC_URLS must be set")?;
+    RpcEndpoint::parse_list(&list)
+        .context("SOLANA_RPC_URLS must be a comma separated list of url|role")
+}
+
+fn env_compute_units(cu_limit: u32) -> Result<ComputeUnitLimit> {
+    let mode = env_str("COMPUTE_UNIT_MODE", Some("static".to_string())).unwrap();
+    let margin_bps = env_u32("COMPUTE_UNIT_MARGIN_BPS", Some(1_000)).unwrap();
+
+    match mode.to_lowercase().as_str() {
+        "static" => Ok(ComputeUnitLimit::Static(cu_limit)),
+        "simulate" => Ok(ComputeUnitLimit::Simulated {
+            fallback: cu_limit,
+            margin_bps,
+        }),
+        other => bail!("COMPUTE_UNIT_MODE must be static|simulate, got {}", other),
+    }
+}
+
+/// Signer configured under `{prefix}_*` (remote settings under `{remote_prefix}_*`)
+///
+/// `None` when neither `{prefix}_SIGNER` nor `{prefix}_KEYPAIR_PATH` is set.
+fn env_signer(prefix: &str, remote_prefix: &str) -> Result<Option<SignerSource>> {
+    let mode_key = format!("{}_SIGNER", prefix);
+    let path_key = format!("{}_KEYPAIR_PATH", prefix);
+    let mode = match env_str(&mode_key, None) {
+        Some(mode) => mode,
+        None if env_str(&path_key, None).is_none() => return Ok(None),
+        None => "file".to_string(),
+    };
+
+    let source = match mode.to_lowercase().as_str() {
+        "file" => SignerSource::File(
+            env_str(&path_key, None).with_context(|| format!("{} must be set", path_key))?,
+        ),
+        "env" => SignerSource::Env(
+            env_str(
+                &format!("{}_SECRET_ENV", prefix),
+                Some(format!("{}_SECRET_KEY", prefix)),
+            )
+            .unwrap(),
+        ),
+        "remote" => {
+            let url_key = format!("{}_URL", remote_prefix);
+            SignerSource::Remote {
+                url: env_str(&url_key, None).with_context(|| format!("{} must be set", url_key))?,
+                token: env_str(&format!("{}_TOKEN", remote_prefix), None),
+                timeout_ms: env_u64(&format!("{}_TIMEOUT_MS", remote_prefix), Some(5_000)).unwrap(),
+            }
+        }
+        other => bail!("{} must be file|env|remote, got {}", mode_key, other),
+    };
+
+    Ok(Some(source))
+}
+
+fn env_balance_thresholds() -> BalanceThresholds {
+    BalanceThresholds {
+        warn_lamports: env_u64("BALANCE_WARN_LAMPORTS", Some(500_000_000)).unwrap(),
+        critical_lamports: env_u64("BALANCE_CRITICAL_LAMPORTS", Some(100_000_000)).unwrap(),
+        warn_runway_hours: env_f64("BALANCE_WARN_RUNWAY_HOURS", Some(48.0)).unwrap(),
+        critical_runway_hours: env_f64("BALANCE_CRITICAL_RUNWAY_HOURS", Some(6.0)).unwrap(),
+        spend_window_secs: env_u64("BALANCE_SPEND_WINDOW_HOURS", Some(24)).unwrap() * 3600,
+        halt_on_critical: env_bool("BALANCE_HALT_ON_CRITICAL", Some(false)).unwrap(),
+    }
+}
+
+fn env_discovery() -> DiscoveryConfig {
+    DiscoveryConfig {
+        use_program_accounts: env_bool("ROUND_DISCOVERY_USE_GPA", Some(false)).unwrap(),
+        rescan_interval_secs: env_u64("ROUND_RESCAN_INTERVAL_SECS", Some(3600)).unwrap(),
+    }
+}
+
+fn env_auto_cancel() -> AutoCancelConfig {
+    AutoCancelConfig {
+        enabled: env_bool("AUTO_CANCEL_ENABLED", Some(false)).unwrap(),
+        grace_secs: env_u64("AUTO_CANCEL_GRACE_SECS", Some(3600)).unwrap(),
+        min_failures: env_u64("AUTO_CANCEL_MIN_FAILURES", Some(5)).unwrap(),
+    }
+}
+
+fn env_priority_fee(base_micro_lamports: u64) -> Result<PriorityFeeStrategy> {
+    let mode = env_str("PRIORITY_FEE_STRATEGY", Some("static".to_string())).unwrap();
+    let percentile = env_u64("PRIORITY_FEE_PERCENTILE", Some(75)).unwrap();
+    let step_bps = env_u64("PRIORITY_FEE_ESCALATION_BPS", Some(5_000)).unwrap();
+    let max_micro_lamports = env_u64("PRIORITY_FEE_MAX_MICROLAMPORTS", Some(1_000_000)).unwrap();
+    if percentile > 100 {
+        bail!(
+            "PRIORITY_FEE_PERCENTILE must be between 0 and 100, got {}",
+            percentile
+        );
+    }
+
+    PriorityFeeStrategy::from_mode(
+        &mode,
+        base_micro_lamports,
+        percentile as u8,
+        step_bps,
+        max_micro_lamports,
+    )
+}
+
 fn env_f64(key: &str, default: Option<f64>) -> Option<f64> {
     env::var(key).ok().and_then(|v| v.parse().ok()).or(default)
 }
@@ -0,0 +1,202 @@
+use anyhow::Result;
+use keeper_lib::{
+    client::{
+        anchor::{get_rounds_by_ids_async, get_rounds_by_status_async},
+        rpc::Rpc,
+    },
+    storage::sqlite::{load_round_watermark, store_round_watermark},
+    types::{enums::RoundStatus, round_account::RoundAccount},
+};
+use solana_sdk::pubkey::Pubkey;
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+const BATCH_SIZE: u64 = 100;
+
+/// How the keepers find rounds that can still need work
+#[derive(Clone, Debug)]
+pub struct DiscoveryConfig {
+    /// Query rounds by status with `getProgramAccounts` instead of by id range
+    pub use_program_accounts: bool,
+    /// Full `1..=current_round_counter` rescan interval, as a safety net
+    pub rescan_interval_secs: u64,
+}
+
+/// Finds candidate rounds without scanning from round 1 on every tick
+///
+/// Rounds below the watermark (the lowest round id not yet `Ended`) are never fetched
+/// again, except by the periodic full rescan. The watermark is persisted in SQLite so a
+/// restart picks up where the keeper left off.
+pub struct RoundDiscovery {
+    cfg: DiscoveryConfig,
+    /// State database, `None` when logs are not persisted (watermark kept in memory)
+    db_path: Option<String>,
+    state: Mutex<DiscoveryState>,
+}
+
+struct DiscoveryState {
+    /// `None` until loaded from SQLite
+    watermark: Option<u64>,
+    last_full_scan: Instant,
+}
+
+impl RoundDiscovery {
+    pub fn new(cfg: DiscoveryConfig, db_path: Option<String>) -> Self {
+        Self {
+            cfg,
+            db_path,
+            // The stored watermark is trusted at startup, the first rescan comes one
+            // interval later
+            state: Mutex::new(DiscoveryState {
+                watermark: None,
+                last_full_scan: Instant::now(),
+            }),
+        }
+    }
+
+    /// Rounds that can still need work; callers still check the status they act on
+    ///
+    /// With `use_program_accounts` only rounds in `statuses` are returned, otherwise every
+    /// round from the watermark up to `round_counter`.
+    pub async fn rounds(
+        &self,
+        rpc: &Rpc,
+        program_id: &Pubkey,
+        round_counter: u64,
+        statuses: &[RoundStatus],
+    ) -> Result<Vec<RoundAccount>> {
+        let full_scan = self.full_scan_due();
+
+        if !full_scan && self.cfg.use_program_accounts {
+            match rounds_by_status(rpc, program_id, statuses).await {
+                Ok(rounds) => {
+                    debug!(total = rounds.len(), "fetched rounds by status");
+                    return Ok(rounds);
+                }
+                Err(err) => {
+                    warn!(error = %err, "round status query failed, scanning from the watermark");
+                }
+            }
+        }
+
+        let from = if full_scan { 1 } else { self.watermark().await };
+        let rounds = rounds_in_range(rpc, program_id, from, round_counter).await?;
+
+        if full_scan {
+            info!(rounds = rounds.len(), round_counter, "full round rescan");
+            self.state.lock().unwrap().last_full_scan = Instant::now();
+        }
+        self.advance(from, round_counter, &rounds).await;
+
+        Ok(rounds)
+    }
+
+    fn full_scan_due(&self) -> bool {
+        let interval = Duration::from_secs(self.cfg.rescan_interval_secs);
+        self.state.lock().unwrap().last_full_scan.elapsed() >= interval
+    }
+
+    /// Current watermark, loaded from SQLite on first use
+    async fn watermark(&self) -> u64 {
+        if let Some(watermark) = self.state.lock().unwrap().watermark {
+            return watermark;
+        }
+
+        let stored = match self.db_path.clone() {
+            Some(path) => {
+                match tokio::task::spawn_blocking(move || load_round_watermark(&path)).await {
+                    Ok(Ok(stored)) => stored,
+                    Ok(Err(err)) => {
+                        warn!(error = %err, "failed to load round watermark");
+                        None
+                    }
+                    Err(err) => {
+                        warn!(error = %err, "round watermark load failed");
+                        None
+                    }
+                }
+            }
+            None => None,
+        };
+
+        *self
+            .state
+            .lock()
+            .unwrap()
+            .watermark
+            .get_or_insert(stored.unwrap_or(1).max(1))
+    }
+
+    /// Move the watermark past the rounds from `from` on that are known to be `Ended`
+    ///
+    /// Stops at the first round that is not `Ended` or was not decoded, so a round is never
+    /// skipped on a guess. The watermark never moves backwards.
+    async fn advance(&self, from: u64, round_counter: u64, rounds: &[RoundAccount]) {
+        let mut next = from;
+        for round in rounds {
+            if round.id != next || !matches!(round.status, RoundStatus::Ended) {
+                break;
+            }
+            next += 1;
+        }
+        let next = next.min(round_counter.saturating_add(1));
+
+        {
+            let mut state = self.state.lock().unwrap();
+            let current = state.watermark.unwrap_or(1);
+            if next <= current {
+                state.watermark = Some(current);
+                return;
+            }
+            state.watermark = Some(next);
+        }
+        debug!(watermark = next, "round watermark advanced");
+
+        let Some(path) = self.db_path.clone() else {
+            return;
+        };
+        match tokio::task::spawn_blocking(move || store_round_watermark(&path, next)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(err)) => warn!(error = %err, "failed to store round watermark"),
+            Err(err) => warn!(error = %err, "round watermark store failed"),
+        }
+    }
+}
+
+/// Rounds in any of the statuses, ordered by id
+async fn rounds_by_status(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    statuses: &[RoundStatus],
+) -> Result<Vec<RoundAccount>> {
+    let mut rounds = Vec::new();
+    for status in statuses {
+        rounds.extend(get_rounds_by_status_async(rpc, program_id, status).await?);
+    }
+    rounds.sort_by_key(|round| round.id);
+    Ok(rounds)
+}
+
+/// Rounds `from..=to` in batches, ordered by id (missing or undecodable rounds left out)
+async fn rounds_in_range(
+    rpc: &Rpc,
+    program_id: &Pubkey,
+    from: u64,
+    to: u64,
+) -> Result<Vec<RoundAccount>> {
+    let mut rounds = Vec::new();
+    let mut start = from;
+    while start <= to {
+        let upper = start.saturating_add(BATCH_SIZE - 1).min(to);
+        let ids: Vec<u64> = (start..=upper).collect();
+        let batch = get_rounds_by_ids_async(rpc, program_id, &ids).await?;
+        debug!(batch_start = start, batch_end = upper, total = batch.len(), "fetched rounds batch");
+        rounds.extend(batch);
+
+        start = upper.saturating_add(1);
+    }
+    Ok(rounds)
+}
@@ -0,0 +1,168 @@
+use anyhow::{Context, Result, bail};
+use keeper_lib::{
+    client::anchor::{
+        create_round, get_group_asset_accounts, get_price_feed_account, get_rounds_by_ids,
+        insert_asset, insert_group_asset,
+    },
+    pda::{derive_config_pda, derive_round_pda},
+    types::{enums::MarketType, round_account::RoundAccount},
+};
+use solana_sdk::signature::Signature;
+use tracing::{debug, info};
+
+use crate::{
+    App,
+    catalog::{AssetCatalog, symbol_bytes, symbol_str},
+};
+
+/// Create group battle round `round_id` if it doesn't exist, then insert its groups and assets
+///
+/// Safe to run again on the same round: existing groups and assets are kept and insertion
+/// resumes after them, see `populate_group_round`.
+pub fn build_group_round(
+    app: &App,
+    catalog: &AssetCatalog,
+    round_id: u64,
+    start_time: i64,
+    end_time: i64,
+) -> Result<Vec<Signature>> {
+    let config_pda = derive_config_pda(&app.program_id);
+    let mut sigs = Vec::new();
+
+    let round = match fetch_round(app, round_id)? {
+        Some(round) => round,
+        None => {
+            let sig = create_round(
+                &app.rpc,
+                &app.signers(),
+                &config_pda,
+                round_id,
+                MarketType::GroupBattle,
+                start_time,
+                end_time,
+                &app.token_mint,
+                &app.system_program_id,
+                &app.token_program_id,
+                &app.program_id,
+            )?;
+            sigs.push(sig);
+            fetch_round(app, round_id)?
+                .with_context(|| format!("Round {} not found after create_round", round_id))?
+        }
+    };
+
+    sigs.extend(populate_group_round(app, catalog, &round)?);
+    Ok(sigs)
+}
+
+/// Insert the catalog's groups and assets missing from an existing group battle round
+///
+/// Groups and assets are inserted in catalog order, so the round's `total_groups` and each
+/// group's `total_assets` tell how far a previous run got. Existing groups must match the
+/// catalog's symbols. Each price feed account is checked to exist before its asset is
+/// inserted.
+pub fn populate_group_round(
+    app: &App,
+    catalog: &AssetCatalog,
+    round: &RoundAccount,
+) -> Result<Vec<Signature>> {
+    if round.market_type != MarketType::GroupBattle {
+        bail!("Round {} is not a group battle round", round.id);
+    }
+    if round.total_groups as usize > catalog.groups.len() {
+        bail!(
+            "Round {} has {} groups, catalog has {}",
+            round.id,
+            round.total_groups,
+            catalog.groups.len()
+        );
+    }
+
+    let config_pda = derive_config_pda(&app.program_id);
+    let round_pda = derive_round_pda(&app.program_id, round.id);
+    let existing =
+        get_group_asset_accounts(&app.rpc, &app.program_id, &round_pda, round.total_groups)?;
+
+    let mut sigs = Vec::new();
+    for (group_id, group) in (1u64..).zip(&catalog.groups) {
+        let symbol = symbol_bytes(&group.symbol)?;
+
+        let total_assets = match existing.get(group_id as usize - 1) {
+            Some((_, on_chain)) => {
+                if on_chain.symbol != symbol {
+                    bail!(
+                        "Round {} group {} is {}, catalog has {}",
+                        round.id,
+                        group_id,
+                        symbol_str(&on_chain.symbol),
+                        group.symbol
+                    );
+                }
+                on_chain.total_assets
+            }
+            None => {
+                let sig = insert_group_asset(
+                    &app.rpc,
+                    &app.signers(),
+                    &config_pda,
+                    round.id,
+                    group_id,
+                    symbol,
+                    &app.system_program_id,
+                    &app.program_id,
+                )?;
+                info!(round_id = round.id, group_id, symbol = %group.symbol, tx_sig = %sig, "group inserted");
+                sigs.push(sig);
+                0
+            }
+        };
+
+        if total_assets as usize > group.assets.len() {
+            bail!(
+                "Round {} group {} has {} assets, catalog has {}",
+                round.id,
+                group.symbol,
+                total_assets,
+                group.assets.len()
+            );
+        }
+
+        for (asset_id, asset) in (1u64..).zip(&group.assets).skip(total_assets as usize) {
+            let price_feed_account =
+                get_price_feed_account(0, &asset.feed_id, &app.push_oracle_program_id)?;
+            app.rpc
+                .read("get_account", |c| c.get_account(&price_feed_account))
+                .with_context(|| {
+                    format!(
+                        "Price feed account {} of {} not found",
+                        price_feed_account, asset.symbol
+                    )
+                })?;
+
+            let sig = insert_asset(
+                &app.rpc,
+                &app.signers(),
+                &config_pda,
+                round.id,
+                group_id,
+                asset_id,
+                symbol_bytes(&asset.symbol)?,
+                &price_feed_account,
+                &app.system_program_id,
+                &app.program_id,
+            )?;
+            info!(round_id = round.id, group_id, asset_id, symbol = %asset.symbol, tx_sig = %sig, "asset inserted");
+            sigs.push(sig);
+        }
+    }
+
+    if sigs.is_empty() {
+        debug!(round_id = round.id, "group round already complete");
+    }
+    Ok(sigs)
+}
+
+fn fetch_round(app: &App, round_id: u64) -> Result<Option<RoundAccount>> {
+    let rounds = get_rounds_by_ids(&app.rpc, &app.program_id, &[round_id])?;
+    Ok(rounds.into_iter().find(|round| round.id == round_id))
+}
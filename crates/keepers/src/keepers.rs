@@ -1,2 +1,16 @@
+pub mod cancel_round;
+pub mod create_round;
 pub mod settle_round;
 pub mod start_round;
+
+use keeper_lib::errors::StalePriceError;
+use std::fmt::Display;
+
+/// Prefix a failed step's error, passing a `StalePriceError` through untouched so the tick
+/// can tell a stale feed (nothing sent, retried next tick) from a failure
+pub(crate) fn step_failed(err: anyhow::Error, step: &str, target: impl Display) -> anyhow::Error {
+    if err.is::<StalePriceError>() {
+        return err;
+    }
+    anyhow::anyhow!("{} failed for {}: {:#}", step, target, err)
+}
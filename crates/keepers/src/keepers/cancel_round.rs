@@ -0,0 +1,127 @@
+use anyhow::Result;
+use keeper_lib::{
+    client::anchor::cancel_round,
+    pda::{derive_config_pda, derive_round_pda, derive_round_vault_pda},
+    types::{enums::RoundStatus, round_account::RoundAccount},
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::{App, workers::join};
+
+pub async fn run_one(app: Arc<App>) -> Result<Vec<Signature>> {
+    let mut sigs: Vec<Signature> = Vec::new();
+
+    if !app.check_balance().await {
+        return Ok(sigs);
+    }
+
+    let cfg = app.fetch_config_async().await?;
+    let sending = app.check_program_status(&cfg.status).await;
+    if !sending && !app.monitor_while_paused() {
+        return Ok(sigs);
+    }
+    if cfg.current_round_counter == 0 {
+        return Ok(sigs);
+    }
+
+    let config_pda = derive_config_pda(&app.program_id);
+
+    let rounds = app
+        .discover_rounds(cfg.current_round_counter, &[RoundStatus::Cancelling])
+        .await?;
+
+    let mut jobs = Vec::new();
+    for round in rounds {
+        debug!(round_id = round.id, status = ?round.status, total_bets = round.total_bets, cancelled_bets = round.cancelled_bets, "round fetched");
+
+        if matches!(round.status, RoundStatus::Cancelling) {
+            if !sending {
+                info!(
+                    round_id = round.id,
+                    "round cancelling, not cancelled while the program is paused"
+                );
+                continue;
+            }
+            let round_id = round.id;
+            let job_app = Arc::clone(&app);
+            match app.workers.spawn(round_id, move || {
+                cancel_cancelling_round(&job_app, &config_pda, &round)
+            }) {
+                Some(job) => jobs.push((round_id, job)),
+                None => debug!(round_id, "round already in progress"),
+            }
+        }
+    }
+
+    for (round_id, job) in jobs {
+        match join(job).await {
+            Ok(sig) => {
+                info!(round_id, tx_sig = %sig, "round cancelled");
+                sigs.push(sig);
+            }
+            Err(err) => {
+                warn!(round_id, error = %err, "cancel_round failed");
+            }
+        }
+    }
+
+    if sending {
+        let cleanup_app = Arc::clone(&app);
+        if let Err(err) =
+            tokio::task::spawn_blocking(move || cleanup_app.close_lookup_tables()).await
+        {
+            warn!(error = %err, "lookup table cleanup failed");
+        }
+    }
+
+    Ok(sigs)
+}
+
+fn cancel_cancelling_round(
+    app: &App,
+    config_pda: &Pubkey,
+    round: &RoundAccount,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(&app.program_id, round.id);
+    let round_vault_pda = derive_round_vault_pda(&app.program_id, &round_pda);
+
+    let sig = cancel(app, config_pda, &round_pda, &round_vault_pda, round)?;
+
+    app.release_round_lookup_table(round.id, &round_pda);
+
+    Ok(sig)
+}
+
+/// Send `cancel_round` for the round, returns the last signature
+pub(crate) fn cancel(
+    app: &App,
+    config_pda: &Pubkey,
+    round_pda: &Pubkey,
+    round_vault_pda: &Pubkey,
+    round: &RoundAccount,
+) -> Result<Signature> {
+    let lookup_table = app.bet_lookup_table(round_pda, round);
+    let sigs = cancel_round(
+        &app.rpc,
+        &app.signers(),
+        config_pda,
+        round_pda,
+        round_vault_pda,
+        round,
+        &app.treasury,
+        &app.token_program_id,
+        &app.system_program_id,
+        lookup_table.as_ref(),
+        &app.program_id,
+    )
+    .map_err(|err| anyhow::anyhow!("cancel_round failed for {}: {:#}", round_pda, err))?;
+
+    sigs.last().cloned().ok_or_else(|| {
+        anyhow::anyhow!(
+            "cancel_round failed for round {}: no signature returned",
+            round.id
+        )
+    })
+}
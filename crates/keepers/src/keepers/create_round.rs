@@ -0,0 +1,214 @@
+use anyhow::Result;
+use chrono::Utc;
+use keeper_lib::{
+    client::anchor::create_round,
+    pda::derive_config_pda,
+    types::{
+        config_account::ConfigAccount,
+        enums::{MarketType, RoundStatus},
+        round_account::RoundAccount,
+    },
+};
+use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
+
+use crate::{App, catalog::AssetCatalog, group_round, schedule::Schedule, workers::join};
+
+/// New rounds must start at least this far ahead, so the create tx lands before start
+const MIN_START_MARGIN_SECS: i64 = 60;
+
+/// Worker key of the round creation job (round ids start at 1)
+///
+/// One job at a time, so overlapping ticks don't create the same slot under two ids.
+const CREATE_JOB_ID: u64 = 0;
+
+/// A round the schedule wants that doesn't exist yet
+struct PlannedRound {
+    template: String,
+    market_type: MarketType,
+    start_time: i64,
+    end_time: i64,
+    /// Groups and assets of a group battle round
+    assets: Option<Arc<AssetCatalog>>,
+}
+
+pub async fn run_one(app: Arc<App>, schedule: Arc<Schedule>) -> Result<Vec<Signature>> {
+    if !app.check_balance().await {
+        return Ok(Vec::new());
+    }
+
+    let cfg = app.fetch_config_async().await?;
+    let sending = app.check_program_status(&cfg.status).await;
+    if !sending && !app.monitor_while_paused() {
+        return Ok(Vec::new());
+    }
+    let rounds = app
+        .discover_rounds(
+            cfg.current_round_counter,
+            &[
+                RoundStatus::Scheduled,
+                RoundStatus::Active,
+                RoundStatus::Cancelling,
+            ],
+        )
+        .await?;
+
+    let now = Utc::now().timestamp();
+    let planned = plan(&schedule, &cfg, &rounds, now);
+    let group_rounds = scheduled_group_rounds(&schedule, rounds);
+    if planned.is_empty() && group_rounds.is_empty() {
+        debug!("no rounds to create");
+        return Ok(Vec::new());
+    }
+    if !sending {
+        for round in &planned {
+            info!(template = %round.template, start_time = round.start_time, "round due, not created while the program is paused");
+        }
+        return Ok(Vec::new());
+    }
+
+    let next_round_id = cfg.current_round_counter + 1;
+    let config_pda = derive_config_pda(&app.program_id);
+    let job_app = Arc::clone(&app);
+    let Some(job) = app.workers.spawn(CREATE_JOB_ID, move || {
+        let mut sigs = complete_group_rounds(&job_app, &group_rounds);
+        sigs.extend(create_planned_rounds(
+            &job_app,
+            &config_pda,
+            next_round_id,
+            &planned,
+        )?);
+        Ok(sigs)
+    }) else {
+        debug!("round creation already in progress");
+        return Ok(Vec::new());
+    };
+
+    join(job).await
+}
+
+/// Slots of every template in its lead window without an existing round
+fn plan(
+    schedule: &Schedule,
+    cfg: &ConfigAccount,
+    rounds: &[RoundAccount],
+    now: i64,
+) -> Vec<PlannedRound> {
+    let mut planned = Vec::new();
+    for template in &schedule.templates {
+        if let Err(err) = template.validate_against(cfg.bet_cutoff_window_secs, &schedule.templates)
+        {
+            error!(template = %template.name, error = %err, "skipping round template");
+            continue;
+        }
+
+        let market_type = MarketType::from(template.market_type);
+        let until = now.saturating_add(template.lead_secs as i64);
+        for (start_time, end_time) in template.slots(now + MIN_START_MARGIN_SECS, until) {
+            let exists = rounds.iter().any(|r| {
+                r.market_type == market_type && r.start_time == start_time && r.end_time == end_time
+            });
+            if exists {
+                continue;
+            }
+            planned.push(PlannedRound {
+                template: template.name.clone(),
+                market_type: market_type.clone(),
+                start_time,
+                end_time,
+                assets: template.assets.clone(),
+            });
+        }
+    }
+    planned.sort_by_key(|p| p.start_time);
+    planned
+}
+
+/// Scheduled group battle rounds of the schedule's templates, with their catalog
+fn scheduled_group_rounds(
+    schedule: &Schedule,
+    rounds: Vec<RoundAccount>,
+) -> Vec<(RoundAccount, Arc<AssetCatalog>)> {
+    rounds
+        .into_iter()
+        .filter(|round| {
+            matches!(round.status, RoundStatus::Scheduled)
+                && round.market_type == MarketType::GroupBattle
+        })
+        .filter_map(|round| {
+            let assets = schedule.templates.iter().find_map(|template| {
+                template
+                    .matches(round.start_time, round.end_time)
+                    .then(|| template.assets.clone())
+                    .flatten()
+            })?;
+            Some((round, assets))
+        })
+        .collect()
+}
+
+/// Insert the groups and assets a previous run left out of scheduled group battle rounds
+fn complete_group_rounds(
+    app: &App,
+    group_rounds: &[(RoundAccount, Arc<AssetCatalog>)],
+) -> Vec<Signature> {
+    let mut sigs = Vec::new();
+    for (round, catalog) in group_rounds {
+        match group_round::populate_group_round(app, catalog, round) {
+            Ok(round_sigs) => sigs.extend(round_sigs),
+            Err(err) => {
+                warn!(round_id = round.id, error = %err, "failed to complete group round");
+            }
+        }
+    }
+    sigs
+}
+
+/// Create the planned rounds in start order, stopping at the first failure
+fn create_planned_rounds(
+    app: &App,
+    config_pda: &Pubkey,
+    first_round_id: u64,
+    planned: &[PlannedRound],
+) -> Result<Vec<Signature>> {
+    let mut sigs = Vec::new();
+    for (round_id, round) in (first_round_id..).zip(planned) {
+        let created = match &round.assets {
+            Some(catalog) => group_round::build_group_round(
+                app,
+                catalog,
+                round_id,
+                round.start_time,
+                round.end_time,
+            ),
+            None => create_round(
+                &app.rpc,
+                &app.signers(),
+                config_pda,
+                round_id,
+                round.market_type.clone(),
+                round.start_time,
+                round.end_time,
+                &app.token_mint,
+                &app.system_program_id,
+                &app.token_program_id,
+                &app.program_id,
+            )
+            .map(|sig| vec![sig]),
+        };
+        match created {
+            Ok(round_sigs) => {
+                info!(round_id, template = %round.template, start_time = round.start_time, end_time = round.end_time, txs = round_sigs.len(), "round created");
+                sigs.extend(round_sigs);
+            }
+            Err(err) => {
+                // The next round id is unknown until the config is fetched again; a group
+                // round left incomplete is finished on the next tick
+                warn!(round_id, template = %round.template, start_time = round.start_time, error = %err, "create_round failed");
+                break;
+            }
+        }
+    }
+    Ok(sigs)
+}
@@ -2,108 +2,184 @@ use anyhow::Result;
 use chrono::Utc;
 use keeper_lib::{
     client::anchor::{
-        capture_end_price, finalize_end_group_assets, finalize_end_groups, get_rounds_by_ids,
-        settle_group_round, settle_single_round,
+        capture_end_price, finalize_end_group_assets, finalize_end_groups, settle_group_round,
+        settle_single_round,
     },
+    errors::StalePriceError,
     pda::{derive_config_pda, derive_round_pda, derive_round_vault_pda},
+    storage::sqlite::{TxContext, log_skipped},
     types::{enums::MarketType, enums::RoundStatus, round_account::RoundAccount},
 };
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
-use tracing::{debug, info, warn};
+use std::sync::Arc;
+use tracing::{debug, error, info, warn};
 
-use crate::App;
+use crate::{
+    App,
+    keepers::{cancel_round::cancel, step_failed},
+    workers::join,
+};
 
-pub fn run_one(app: &App) -> Result<Vec<Signature>> {
+pub async fn run_one(app: Arc<App>) -> Result<Vec<Signature>> {
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let cfg = app.fetch_config()?;
+    if !app.check_balance().await {
+        return Ok(sigs);
+    }
+
+    let cfg = app.fetch_config_async().await?;
+    let sending = app.check_program_status(&cfg.status).await;
+    if !sending && !app.monitor_while_paused() {
+        return Ok(sigs);
+    }
     if cfg.current_round_counter == 0 {
         return Ok(sigs);
     }
 
     let config_pda = derive_config_pda(&app.program_id);
+    let max_price_age_secs = cfg.max_price_update_age_secs;
 
     let now = Utc::now().timestamp();
-    let mut start = 1u64;
-    let end = cfg.current_round_counter;
-    let batch_size = 100;
-
-    while start <= end {
-        debug!(batch_start = start, "processing rounds batch");
-
-        let upper = start.saturating_add(batch_size - 1).min(end);
-        let ids: Vec<u64> = (start..=upper).collect();
-        let rounds = get_rounds_by_ids(app.rpc.client(), &app.program_id, &ids)?;
-        debug!(batch_start = start, batch_end = upper, total = rounds.len(), "fetched rounds batch");
-
-        for round in rounds {
-            debug!(round_id = round.id, status = ?round.status, end_time = round.end_time, market_type = ?round.market_type, "round fetched");
-
-            if matches!(
-                round.status,
-                RoundStatus::Active | RoundStatus::PendingSettlement
-            ) && round.end_time <= now
-            {
-                let round_pda = derive_round_pda(&app.program_id, round.id);
-                let round_vault_pda = derive_round_vault_pda(&app.program_id, &round_pda);
-
-                let sig_res = match round.market_type {
-                    MarketType::SingleAsset => {
-                        settle_single(app, &config_pda, &round_pda, &round_vault_pda, &round)
-                    }
-                    MarketType::GroupBattle => {
-                        match settle_group(&app, &config_pda, &round_pda, &round_vault_pda, &round)
-                        {
-                            Ok(sigs) => sigs
-                                .last()
-                                .cloned()
-                                .ok_or_else(|| anyhow::anyhow!("no signature returned"))
-                                .map_err(|err| {
-                                    anyhow::anyhow!(
-                                        "settle_group failed for round {}: {:#}",
-                                        round.id,
-                                        err
-                                    )
-                                }),
-                            Err(err) => Err(anyhow::anyhow!(
-                                "settle_group failed for round {}: {:#}",
-                                round.id,
-                                err
-                            )),
-                        }
-                    }
-                };
-                match sig_res {
-                    Ok(sig) => {
-                        info!(round_id = round.id, tx_sig = %sig, "round settled");
-                        sigs.push(sig);
-                    }
-                    Err(err) => {
-                        warn!(round_id = round.id, error = %err, "settle_round failed");
-                        continue;
-                    }
-                }
+    let rounds = app
+        .discover_rounds(
+            cfg.current_round_counter,
+            &[RoundStatus::Active, RoundStatus::PendingSettlement],
+        )
+        .await?;
+
+    let mut jobs = Vec::new();
+    for round in rounds {
+        debug!(round_id = round.id, status = ?round.status, end_time = round.end_time, market_type = ?round.market_type, "round fetched");
+
+        if matches!(
+            round.status,
+            RoundStatus::Active | RoundStatus::PendingSettlement
+        ) && round.end_time <= now
+        {
+            if !sending {
+                info!(
+                    round_id = round.id,
+                    end_time = round.end_time,
+                    "round due, not settled while the program is paused"
+                );
+                continue;
             }
+            let round_id = round.id;
+            let job_app = Arc::clone(&app);
+            match app.workers.spawn(round_id, move || {
+                settle_due_round(&job_app, &config_pda, &round, max_price_age_secs)
+            }) {
+                Some(job) => jobs.push((round_id, job)),
+                None => debug!(round_id, "round already in progress"),
+            }
+        }
+    }
+
+    for (round_id, job) in jobs {
+        match join(job).await {
+            Ok(sig) => {
+                info!(round_id, tx_sig = %sig, "round settled");
+                sigs.push(sig);
+            }
+            Err(err) => match err.downcast_ref::<StalePriceError>() {
+                Some(stale) => {
+                    warn!(event = "stale_feed", round_id, error = %stale, "round not settled, price feed stale");
+                    // Nothing was sent, but it counts towards auto-cancel like a failed send
+                    log_skipped(
+                        TxContext {
+                            keeper_type: "settle".into(),
+                            op: "settle_round".into(),
+                            round_id: Some(round_id as i64),
+                            ..Default::default()
+                        },
+                        "StaleFeed",
+                        stale.to_string(),
+                    );
+                }
+                None => warn!(round_id, error = %err, "settle_round failed"),
+            },
         }
+    }
 
-        start = upper.saturating_add(1);
+    if sending {
+        let cleanup_app = Arc::clone(&app);
+        if let Err(err) =
+            tokio::task::spawn_blocking(move || cleanup_app.close_lookup_tables()).await
+        {
+            warn!(error = %err, "lookup table cleanup failed");
+        }
     }
 
     Ok(sigs)
 }
 
+fn settle_due_round(
+    app: &App,
+    config_pda: &Pubkey,
+    round: &RoundAccount,
+    max_price_age_secs: u64,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(&app.program_id, round.id);
+    let round_vault_pda = derive_round_vault_pda(&app.program_id, &round_pda);
+
+    let now = Utc::now().timestamp();
+    if let Some(failures) = app.auto_cancel.should_cancel(round, now) {
+        error!(event = "round_auto_cancel", round_id = round.id, end_time = round.end_time, overdue_secs = now - round.end_time, failures = failures.count, last_error_name = failures.last_error_name.as_deref(), "round can't be settled past its grace window, cancelling");
+        let outcome = cancel(app, config_pda, &round_pda, &round_vault_pda, round);
+        app.auto_cancel.record(round, now, failures, &outcome);
+        if outcome.is_ok() {
+            app.release_round_lookup_table(round.id, &round_pda);
+        }
+        return outcome;
+    }
+
+    let sig = match round.market_type {
+        MarketType::SingleAsset => settle_single(
+            app,
+            config_pda,
+            &round_pda,
+            &round_vault_pda,
+            round,
+            max_price_age_secs,
+        )?,
+        MarketType::GroupBattle => {
+            let sigs = settle_group(
+                app,
+                config_pda,
+                &round_pda,
+                &round_vault_pda,
+                round,
+                max_price_age_secs,
+            )
+            .map_err(|err| step_failed(err, "settle_group", format_args!("round {}", round.id)))?;
+            sigs.last().cloned().ok_or_else(|| {
+                anyhow::anyhow!(
+                    "settle_group failed for round {}: no signature returned",
+                    round.id
+                )
+            })?
+        }
+    };
+
+    app.release_round_lookup_table(round.id, &round_pda);
+
+    Ok(sig)
+}
+
 fn settle_single(
     app: &App,
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round_vault_pda: &Pubkey,
     round: &RoundAccount,
+    max_price_age_secs: u64,
 ) -> Result<Signature> {
     info!(round_pda = %round_pda, "settling single round");
 
+    let lookup_table = app.bet_lookup_table(round_pda, round);
     settle_single_round(
         &app.rpc,
-        app.signer(),
+        &app.signers(),
         &config_pda,
         &round_pda,
         &round_vault_pda,
@@ -111,13 +187,16 @@ fn settle_single(
         &app.treasury,
         &app.treasury_token_account,
         &app.gold_price_feed,
+        max_price_age_secs,
+        &app.pyth_receiver_program_id,
         &app.token_mint,
         &app.token_program_id,
         &app.associated_token_program_id,
         &app.system_program_id,
+        lookup_table.as_ref(),
         &app.program_id,
     )
-    .map_err(|err| anyhow::anyhow!("settle_single_round failed for {}: {:#}", round_pda, err))
+    .map_err(|err| step_failed(err, "settle_single_round", round_pda))
 }
 
 fn settle_group(
@@ -126,50 +205,59 @@ fn settle_group(
     round_pda: &Pubkey,
     round_vault_pda: &Pubkey,
     round: &RoundAccount,
+    max_price_age_secs: u64,
 ) -> Result<Vec<Signature>> {
     info!(round_pda = %round_pda, "settling group round");
 
     // if round has not captured end groups
     if round.captured_end_groups < round.total_groups {
+        let lookup_table = app.group_lookup_table(round_pda, round);
+
         // Capture end price
         capture_end_price(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             &round,
+            max_price_age_secs,
             &app.push_oracle_program_id,
+            &app.pyth_receiver_program_id,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
 
         // Finalize end group assets
         finalize_end_group_assets(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             &round,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
 
         // Finalize end groups
         finalize_end_groups(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             &round,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
     }
 
     // Settle group round
+    let lookup_table = app.bet_lookup_table(round_pda, round);
     settle_group_round(
         &app.rpc,
-        app.signer(),
+        &app.signers(),
         &config_pda,
         &round_pda,
         &round_vault_pda,
@@ -180,6 +268,7 @@ fn settle_group(
         &app.token_program_id,
         &app.associated_token_program_id,
         &app.system_program_id,
+        lookup_table.as_ref(),
         &app.program_id,
     )
     .map_err(|err| anyhow::anyhow!("settle_group_round failed for {}: {:#}", round_pda, err))
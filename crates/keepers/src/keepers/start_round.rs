@@ -2,81 +2,121 @@ use anyhow::Result;
 use chrono::Utc;
 use keeper_lib::{
     client::anchor::{
-        capture_start_price, finalize_start_group_assets, finalize_start_groups, get_rounds_by_ids,
-        start_round,
+        capture_start_price, finalize_start_group_assets, finalize_start_groups, start_round,
     },
+    errors::StalePriceError,
     pda::{derive_config_pda, derive_round_pda},
     types::{enums::MarketType, enums::RoundStatus, round_account::RoundAccount},
 };
 use solana_sdk::{pubkey::Pubkey, signature::Signature};
+use std::sync::Arc;
 use tracing::{debug, info, warn};
 
-use crate::App;
+use crate::{App, keepers::step_failed, workers::join};
 
-pub fn run_one(app: &App) -> Result<Vec<Signature>> {
+pub async fn run_one(app: Arc<App>) -> Result<Vec<Signature>> {
     let mut sigs: Vec<Signature> = Vec::new();
 
-    let cfg = app.fetch_config()?;
+    if !app.check_balance().await {
+        return Ok(sigs);
+    }
+
+    let cfg = app.fetch_config_async().await?;
+    let sending = app.check_program_status(&cfg.status).await;
+    if !sending && !app.monitor_while_paused() {
+        return Ok(sigs);
+    }
     if cfg.current_round_counter == 0 {
         return Ok(sigs);
     }
 
     let config_pda = derive_config_pda(&app.program_id);
+    let max_price_age_secs = cfg.max_price_update_age_secs;
 
     let now = Utc::now().timestamp();
-    let mut start = 1u64;
-    let end = cfg.current_round_counter;
-    let batch_size = 100;
-
-    while start <= end {
-        let upper = start.saturating_add(batch_size - 1).min(end);
-        let ids: Vec<u64> = (start..=upper).collect();
-        let rounds = get_rounds_by_ids(app.rpc.client(), &app.program_id, &ids)?;
-        debug!(batch_start = start, batch_end = upper, total = rounds.len(), "fetched rounds batch");
-
-        for round in rounds {
-            debug!(round_id = round.id, status = ?round.status, start_time = round.start_time, now, market_type = ?round.market_type, "round fetched");
-
-            if matches!(round.status, RoundStatus::Scheduled) && round.start_time <= now {
-                let round_pda = derive_round_pda(&app.program_id, round.id);
-
-                let sig_res = match round.market_type {
-                    MarketType::SingleAsset => start_single_round(app, &config_pda, &round_pda),
-                    MarketType::GroupBattle => {
-                        start_group_round(app, &config_pda, &round_pda, &round)
-                    }
-                };
-                match sig_res {
-                    Ok(sig) => {
-                        info!(round_id = round.id, tx_sig = %sig, "round started");
-                        sigs.push(sig);
-                    }
-                    Err(err) => {
-                        warn!(round_id = round.id, error = %err, "start_round failed");
-                        continue;
-                    }
-                }
+    let rounds = app
+        .discover_rounds(cfg.current_round_counter, &[RoundStatus::Scheduled])
+        .await?;
+
+    let mut jobs = Vec::new();
+    for round in rounds {
+        debug!(round_id = round.id, status = ?round.status, start_time = round.start_time, now, market_type = ?round.market_type, "round fetched");
+
+        if matches!(round.status, RoundStatus::Scheduled) && round.start_time <= now {
+            if !sending {
+                info!(
+                    round_id = round.id,
+                    start_time = round.start_time,
+                    "round due, not started while the program is paused"
+                );
+                continue;
+            }
+            let round_id = round.id;
+            let job_app = Arc::clone(&app);
+            match app.workers.spawn(round_id, move || {
+                start_due_round(&job_app, &config_pda, &round, max_price_age_secs)
+            }) {
+                Some(job) => jobs.push((round_id, job)),
+                None => debug!(round_id, "round already in progress"),
             }
         }
+    }
 
-        start = upper.saturating_add(1);
+    for (round_id, job) in jobs {
+        match join(job).await {
+            Ok(sig) => {
+                info!(round_id, tx_sig = %sig, "round started");
+                sigs.push(sig);
+            }
+            Err(err) => match err.downcast_ref::<StalePriceError>() {
+                Some(stale) => {
+                    warn!(event = "stale_feed", round_id, error = %stale, "round not started, price feed stale");
+                }
+                None => warn!(round_id, error = %err, "start_round failed"),
+            },
+        }
     }
 
     Ok(sigs)
 }
 
-fn start_single_round(app: &App, config_pda: &Pubkey, round_pda: &Pubkey) -> Result<Signature> {
+fn start_due_round(
+    app: &App,
+    config_pda: &Pubkey,
+    round: &RoundAccount,
+    max_price_age_secs: u64,
+) -> Result<Signature> {
+    let round_pda = derive_round_pda(&app.program_id, round.id);
+
+    match round.market_type {
+        MarketType::SingleAsset => {
+            start_single_round(app, config_pda, &round_pda, max_price_age_secs)
+        }
+        MarketType::GroupBattle => {
+            start_group_round(app, config_pda, &round_pda, round, max_price_age_secs)
+        }
+    }
+}
+
+fn start_single_round(
+    app: &App,
+    config_pda: &Pubkey,
+    round_pda: &Pubkey,
+    max_price_age_secs: u64,
+) -> Result<Signature> {
     info!(round_pda = %round_pda, "starting single round");
     start_round(
         &app.rpc,
-        app.signer(),
+        &app.signers(),
         &config_pda,
         &round_pda,
         Some(&app.gold_price_feed),
+        max_price_age_secs,
+        &app.pyth_receiver_program_id,
         &app.system_program_id,
         &app.program_id,
     )
-    .map_err(|err| anyhow::anyhow!("start_round failed for {}: {:#}", round_pda, err))
+    .map_err(|err| step_failed(err, "start_round", round_pda))
 }
 
 fn start_group_round(
@@ -84,42 +124,50 @@ fn start_group_round(
     config_pda: &Pubkey,
     round_pda: &Pubkey,
     round: &RoundAccount,
+    max_price_age_secs: u64,
 ) -> Result<Signature> {
     info!(round_pda = %round_pda, "starting group round");
 
     // if round has not captured start groups
     if round.captured_start_groups < round.total_groups {
+        let lookup_table = app.group_lookup_table(round_pda, round);
+
         // capture start price
         capture_start_price(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             round,
+            max_price_age_secs,
             &app.push_oracle_program_id,
+            &app.pyth_receiver_program_id,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
 
         // finalize start group assets
         finalize_start_group_assets(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             round,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
 
         // finalize start groups
         finalize_start_groups(
             &app.rpc,
-            app.signer(),
+            &app.signers(),
             &config_pda,
             &round_pda,
             round,
             &app.system_program_id,
+            lookup_table.as_ref(),
             &app.program_id,
         )?;
     }
@@ -127,10 +175,12 @@ fn start_group_round(
     // start round
     start_round(
         &app.rpc,
-        app.signer(),
+        &app.signers(),
         &config_pda,
         &round_pda,
         None,
+        max_price_age_secs,
+        &app.pyth_receiver_program_id,
         &app.system_program_id,
         &app.program_id,
     )
@@ -1,26 +1,58 @@
+use crate::auto_cancel::AutoCancelPolicy;
+use crate::balance::BalanceWatcher;
 use crate::config::RuntimeConfig;
+use crate::discovery::RoundDiscovery;
+use crate::program_status::ProgramStatusGate;
+use crate::workers::RoundWorkers;
 use anyhow::Result;
 use keeper_lib::{
     client::{
-        anchor::{get_config_account, get_price_feed_account},
+        alt::LookupTables,
+        anchor::{
+            bet_lookup_addresses, get_config_account, get_config_account_async,
+            get_price_feed_account, group_lookup_addresses,
+        },
         rpc::Rpc,
     },
     pda::derive_token_account_pda,
     storage::sqlite::{SQLiteLogConfig, init_global_logger},
-    types::config_account::ConfigAccount,
-    wallet::load_keypair_from_file,
+    types::{
+        config_account::ConfigAccount,
+        enums::{ProgramStatus, RoundStatus},
+        round_account::RoundAccount,
+    },
+    wallet::{KeeperSigner, TxSigners},
 };
+use solana_sdk::message::AddressLookupTableAccount;
 use solana_sdk::pubkey::Pubkey;
-use solana_sdk::signature::Keypair;
-use std::sync::Arc;
+use solana_sdk::signer::Signer;
+use std::{sync::Arc, time::Duration};
+use tracing::{info, warn};
 
+pub mod admin;
+pub mod auto_cancel;
+pub mod balance;
+pub mod catalog;
 pub mod config;
+pub mod discovery;
+pub mod group_round;
 pub mod keepers;
 pub mod logging;
+pub mod preflight;
+pub mod program_status;
+pub mod schedule;
+pub mod workers;
 
 pub struct App {
     rpc: Rpc,
-    signer: Arc<Keypair>,
+    authority: Arc<dyn KeeperSigner>,
+    fee_payer: Arc<dyn KeeperSigner>,
+    lookup_tables: Option<LookupTables>,
+    workers: RoundWorkers,
+    balance: BalanceWatcher,
+    discovery: RoundDiscovery,
+    auto_cancel: AutoCancelPolicy,
+    program_status: ProgramStatusGate,
     treasury: Pubkey,
     treasury_token_account: Pubkey,
     gold_price_feed: Pubkey,
@@ -28,6 +60,7 @@ pub struct App {
     token_program_id: Pubkey,
     associated_token_program_id: Pubkey,
     push_oracle_program_id: Pubkey,
+    pyth_receiver_program_id: Pubkey,
     system_program_id: Pubkey,
     program_id: Pubkey,
 }
@@ -35,17 +68,28 @@ pub struct App {
 impl App {
     pub fn init_from(cfg: RuntimeConfig) -> Result<Self> {
         let rpc = Rpc::new(
-            &cfg.solana_rpc_url,
+            cfg.rpc_endpoints.clone(),
             cfg.rpc_timeout_ms,
+            cfg.rpc_probe_interval_secs,
             cfg.commitment,
             cfg.preflight,
             cfg.tx_max_retries,
-            cfg.cu_limit,
-            cfg.cu_price_micro_lamports,
+            cfg.compute_units.clone(),
+            cfg.priority_fee.clone(),
             cfg.backoff_ms,
             cfg.max_remaining_accounts,
+            cfg.lookup_table_max_remaining_accounts,
         );
-        let signer = Arc::new(load_keypair_from_file(&cfg.keeper_keypair_path)?);
+        let authority = cfg.signer.load()?;
+        info!(signer = authority.kind(), pubkey = %authority.pubkey(), "loaded keeper authority");
+        let fee_payer = match &cfg.fee_payer {
+            Some(source) => {
+                let fee_payer = source.load()?;
+                info!(signer = fee_payer.kind(), pubkey = %fee_payer.pubkey(), "loaded fee payer");
+                fee_payer
+            }
+            None => Arc::clone(&authority),
+        };
         let gold_price_feed =
             get_price_feed_account(0, &cfg.gold_price_feed_id, &cfg.push_oracle_program_id)?;
         let treasury_token_account = derive_token_account_pda(
@@ -76,7 +120,26 @@ impl App {
 
         Ok(Self {
             rpc,
-            signer,
+            authority,
+            fee_payer,
+            lookup_tables: cfg.use_lookup_tables.then(LookupTables::new),
+            workers: RoundWorkers::new(cfg.keeper_concurrency),
+            balance: BalanceWatcher::new(
+                cfg.balance.clone(),
+                cfg.persist_logs.then(|| cfg.log_db_path.clone()),
+            ),
+            discovery: RoundDiscovery::new(
+                cfg.discovery.clone(),
+                cfg.persist_logs.then(|| cfg.log_db_path.clone()),
+            ),
+            auto_cancel: AutoCancelPolicy::new(
+                cfg.auto_cancel.clone(),
+                cfg.persist_logs.then(|| cfg.log_db_path.clone()),
+            ),
+            program_status: ProgramStatusGate::new(
+                cfg.pause_monitor,
+                cfg.persist_logs.then(|| cfg.log_db_path.clone()),
+            ),
             treasury: cfg.treasury,
             treasury_token_account,
             gold_price_feed: gold_price_feed,
@@ -84,17 +147,149 @@ impl App {
             token_program_id: cfg.token_program_id,
             associated_token_program_id: cfg.associated_token_program_id,
             push_oracle_program_id: cfg.push_oracle_program_id,
+            pyth_receiver_program_id: cfg.pyth_receiver_program_id,
             system_program_id: cfg.system_program_id,
             program_id: cfg.program_id,
         })
     }
 
-    pub fn signer(&self) -> &Keypair {
-        &self.signer
+    /// Re-probe demoted RPC endpoints in the background, every probe interval
+    pub fn spawn_rpc_probe(self: &Arc<Self>) {
+        let app = Arc::clone(self);
+        tokio::spawn(async move {
+            let period = app.rpc.pool().probe_interval().max(Duration::from_secs(1));
+            let mut ticker = tokio::time::interval(period);
+            loop {
+                ticker.tick().await;
+                app.rpc.pool().reprobe_demoted().await;
+            }
+        });
+    }
+
+    /// Fee payer and keeper authority for the keeper's transactions
+    pub fn signers(&self) -> TxSigners<'_> {
+        TxSigners::new(self.fee_payer.as_ref(), self.authority.as_ref())
+    }
+
+    /// Check the fee payer balance; false while sending is paused for a critical balance
+    pub async fn check_balance(&self) -> bool {
+        self.balance
+            .check(&self.rpc, &self.fee_payer.pubkey())
+            .await
+    }
+
+    /// Track the program status; false while the program is paused and sends must be skipped
+    pub async fn check_program_status(&self, status: &ProgramStatus) -> bool {
+        self.program_status.check(status).await
+    }
+
+    /// Whether a keeper keeps its read-only pass running while the program is paused
+    pub fn monitor_while_paused(&self) -> bool {
+        self.program_status.monitor_while_paused()
+    }
+
+    /// Rounds up to `round_counter` that can still need work (see `RoundDiscovery`)
+    pub async fn discover_rounds(
+        &self,
+        round_counter: u64,
+        statuses: &[RoundStatus],
+    ) -> Result<Vec<RoundAccount>> {
+        self.discovery
+            .rounds(&self.rpc, &self.program_id, round_counter, statuses)
+            .await
     }
 
     pub fn fetch_config(&self) -> Result<ConfigAccount> {
-        let cfg = get_config_account(self.rpc.client(), &self.program_id)?;
+        let cfg = get_config_account(&self.rpc, &self.program_id)?;
         Ok(cfg)
     }
+
+    pub async fn fetch_config_async(&self) -> Result<ConfigAccount> {
+        let cfg = get_config_account_async(&self.rpc, &self.program_id).await?;
+        Ok(cfg)
+    }
+
+    /// Lookup table of a group battle round's group asset, asset and price feed accounts
+    pub fn group_lookup_table(
+        &self,
+        round_pda: &Pubkey,
+        round: &RoundAccount,
+    ) -> Option<AddressLookupTableAccount> {
+        self.lookup_tables.as_ref()?;
+        match group_lookup_addresses(
+            &self.rpc,
+            round_pda,
+            round,
+            &self.push_oracle_program_id,
+            &self.program_id,
+        ) {
+            Ok(addresses) => self.round_lookup_table(round.id, round_pda, &addresses),
+            Err(err) => {
+                warn!(round_id = round.id, error = %err, "failed to collect group lookup addresses");
+                None
+            }
+        }
+    }
+
+    /// Lookup table of a round's bet accounts
+    pub fn bet_lookup_table(
+        &self,
+        round_pda: &Pubkey,
+        round: &RoundAccount,
+    ) -> Option<AddressLookupTableAccount> {
+        self.lookup_tables.as_ref()?;
+        let addresses = bet_lookup_addresses(round_pda, round, &self.program_id);
+        self.round_lookup_table(round.id, round_pda, &addresses)
+    }
+
+    /// Deactivate the lookup table of a settled round (closed later by `close_lookup_tables`)
+    pub fn release_round_lookup_table(&self, round_id: u64, round_pda: &Pubkey) {
+        let Some(lookup_tables) = self.lookup_tables.as_ref() else {
+            return;
+        };
+        if let Err(err) =
+            lookup_tables.deactivate_round_table(&self.rpc, &self.signers(), round_id, round_pda)
+        {
+            warn!(round_id, error = %err, "failed to deactivate lookup table");
+        }
+    }
+
+    /// Close deactivated lookup tables whose cooldown elapsed
+    pub fn close_lookup_tables(&self) {
+        let Some(lookup_tables) = self.lookup_tables.as_ref() else {
+            return;
+        };
+        if let Err(err) = lookup_tables.close_expired(&self.rpc, &self.signers()) {
+            warn!(error = %err, "failed to close lookup tables");
+        }
+    }
+
+    /// Lookup table holding the given round accounts, `None` to send legacy transactions
+    /// (lookup tables disabled, or the addresses don't fit in one table)
+    pub fn round_lookup_table(
+        &self,
+        round_id: u64,
+        round_pda: &Pubkey,
+        addresses: &[Pubkey],
+    ) -> Option<AddressLookupTableAccount> {
+        let lookup_tables = self.lookup_tables.as_ref()?;
+        // A single legacy chunk already fits them, not worth the table rent
+        if addresses.len() <= self.rpc.max_remaining_accounts() {
+            return None;
+        }
+
+        match lookup_tables.ensure_round_table(
+            &self.rpc,
+            &self.signers(),
+            round_id,
+            round_pda,
+            addresses,
+        ) {
+            Ok(table) => Some(table),
+            Err(err) => {
+                warn!(round_id, error = %err, "lookup table unavailable, sending legacy transactions");
+                None
+            }
+        }
+    }
 }
@@ -0,0 +1,172 @@
+use anyhow::{Result, bail};
+use keeper_lib::client::anchor::get_config_account_async;
+use solana_sdk::{account::Account, pubkey::Pubkey};
+use tracing::{error, info};
+
+use crate::App;
+
+/// One startup check: what was found, or what is wrong
+struct Check {
+    name: &'static str,
+    outcome: Result<String, String>,
+}
+
+/// Check the env configuration against the chain before the first tick
+///
+/// Every check runs and is logged; if any fails, startup fails with a report of all of them,
+/// instead of the keeper hitting `unauthorizedKeeper` or `invalidTreasuryAuthority` at
+/// settlement time.
+pub async fn run(app: &App) -> Result<()> {
+    let keys = [
+        app.program_id,
+        app.treasury_token_account,
+        app.gold_price_feed,
+    ];
+    let accounts = app
+        .rpc
+        .read_async("get_multiple_accounts", |c| c.get_multiple_accounts(&keys))
+        .await?;
+    let [program, treasury_token_account, gold_price_feed] =
+        [0, 1, 2].map(|i| accounts[i].as_ref());
+
+    let mut checks = vec![
+        Check {
+            name: "program",
+            outcome: check_program(&app.program_id, program),
+        },
+        Check {
+            name: "treasury_token_account",
+            outcome: check_owned(
+                "Treasury token account (TREASURY's ATA for TOKEN_MINT)",
+                &app.treasury_token_account,
+                treasury_token_account,
+                &app.token_program_id,
+                "TOKEN_PROGRAM_ID",
+            ),
+        },
+        // The feed PDA derives from PUSH_ORACLE_PROGRAM_ID, its data is owned by the receiver
+        Check {
+            name: "gold_price_feed",
+            outcome: check_owned(
+                "Gold price feed account (GOLD_PRICE_FEED_ID under PUSH_ORACLE_PROGRAM_ID)",
+                &app.gold_price_feed,
+                gold_price_feed,
+                &app.pyth_receiver_program_id,
+                "PYTH_RECEIVER_PROGRAM_ID",
+            ),
+        },
+    ];
+
+    match get_config_account_async(&app.rpc, &app.program_id).await {
+        Ok(cfg) => {
+            let signer = app.signers().authority_pubkey();
+            let authorized = cfg
+                .keeper_authorities
+                .iter()
+                .any(|k| k.to_bytes() == signer.to_bytes());
+            checks.push(Check {
+                name: "keeper_authority",
+                outcome: if authorized {
+                    Ok(format!("{} is an authorized keeper", signer))
+                } else {
+                    let authorities: Vec<String> = cfg
+                        .keeper_authorities
+                        .iter()
+                        .map(|k| k.to_string())
+                        .collect();
+                    Err(format!(
+                        "Signer {} is not in the config keeper_authorities [{}]",
+                        signer,
+                        authorities.join(", ")
+                    ))
+                },
+            });
+            checks.push(Check {
+                name: "treasury",
+                outcome: check_matches(
+                    "TREASURY",
+                    &app.treasury,
+                    &cfg.treasury.to_bytes(),
+                    &cfg.treasury.to_string(),
+                ),
+            });
+            checks.push(Check {
+                name: "token_mint",
+                outcome: check_matches(
+                    "TOKEN_MINT",
+                    &app.token_mint,
+                    &cfg.token_mint.to_bytes(),
+                    &cfg.token_mint.to_string(),
+                ),
+            });
+        }
+        Err(err) => checks.push(Check {
+            name: "config",
+            outcome: Err(format!("Failed to fetch the config account: {:#}", err)),
+        }),
+    }
+
+    let mut failed = Vec::new();
+    for check in &checks {
+        match &check.outcome {
+            Ok(detail) => info!(check = check.name, detail = %detail, "startup check passed"),
+            Err(problem) => {
+                error!(check = check.name, problem = %problem, "startup check failed");
+                failed.push(format!("  - {}: {}", check.name, problem));
+            }
+        }
+    }
+    if !failed.is_empty() {
+        bail!(
+            "{} of {} startup checks failed:\n{}",
+            failed.len(),
+            checks.len(),
+            failed.join("\n")
+        );
+    }
+
+    Ok(())
+}
+
+fn check_program(program_id: &Pubkey, account: Option<&Account>) -> Result<String, String> {
+    match account {
+        None => Err(format!("PROGRAM_ID {} not found", program_id)),
+        Some(acc) if !acc.executable => Err(format!("PROGRAM_ID {} is not executable", program_id)),
+        Some(_) => Ok(format!("{} is executable", program_id)),
+    }
+}
+
+/// The account exists and is owned by `owner`
+fn check_owned(
+    what: &str,
+    key: &Pubkey,
+    account: Option<&Account>,
+    owner: &Pubkey,
+    owner_var: &str,
+) -> Result<String, String> {
+    match account {
+        None => Err(format!("{} {} not found", what, key)),
+        Some(acc) if acc.owner != *owner => Err(format!(
+            "{} {} is owned by {}, not {} {}",
+            what, key, acc.owner, owner_var, owner
+        )),
+        Some(_) => Ok(format!("{} exists, owned by {}", key, owner)),
+    }
+}
+
+/// The env value matches the config's
+fn check_matches(
+    var: &str,
+    value: &Pubkey,
+    config_bytes: &[u8; 32],
+    config_value: &str,
+) -> Result<String, String> {
+    if value.to_bytes() == *config_bytes {
+        Ok(format!("{} matches the config", value))
+    } else {
+        Err(format!(
+            "{} {} does not match the config ({})",
+            var, value, config_value
+        ))
+    }
+}
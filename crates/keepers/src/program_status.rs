@@ -0,0 +1,69 @@
+use chrono::Utc;
+use keeper_lib::{storage::sqlite::record_program_status, types::enums::ProgramStatus};
+use std::sync::Mutex;
+use tracing::{debug, info, warn};
+
+/// Holds the keepers' sends while the program is paused
+///
+/// The program rejects keeper instructions with `programPaused`/`emergencyPaused` while
+/// `ConfigAccount::status` is not `Active`, so sending would only burn fees. Each pause this
+/// keeper sees is recorded in SQLite (`program_pauses`) with its duration.
+pub struct ProgramStatusGate {
+    /// Keep discovering rounds and logging what is due while paused
+    monitor_while_paused: bool,
+    /// Log database, `None` when logs are not persisted (pauses not recorded)
+    log_db_path: Option<String>,
+    last: Mutex<Option<ProgramStatus>>,
+}
+
+impl ProgramStatusGate {
+    pub fn new(monitor_while_paused: bool, log_db_path: Option<String>) -> Self {
+        Self {
+            monitor_while_paused,
+            log_db_path,
+            last: Mutex::new(None),
+        }
+    }
+
+    /// Track the status from the latest config; false while sends must be skipped
+    pub async fn check(&self, status: &ProgramStatus) -> bool {
+        let previous = self.last.lock().unwrap().replace(status.clone());
+        if previous.as_ref() != Some(status) {
+            match &previous {
+                Some(previous) => {
+                    warn!(event = "program_status_changed", from = ?previous, to = ?status, "program status changed");
+                }
+                None => info!(status = ?status, "program status"),
+            }
+            self.record(status).await;
+        }
+
+        if matches!(status, ProgramStatus::Active) {
+            return true;
+        }
+        debug!(status = ?status, monitor = self.monitor_while_paused, "program paused, skipping sends");
+        false
+    }
+
+    /// Whether a paused keeper still runs its read-only pass
+    pub fn monitor_while_paused(&self) -> bool {
+        self.monitor_while_paused
+    }
+
+    async fn record(&self, status: &ProgramStatus) {
+        let Some(path) = self.log_db_path.clone() else {
+            return;
+        };
+        let status = format!("{:?}", status);
+        let now = Utc::now().timestamp();
+        match tokio::task::spawn_blocking(move || record_program_status(&path, &status, now)).await
+        {
+            Ok(Ok(Some((status, duration_secs)))) => {
+                info!(event = "program_pause_ended", status = %status, duration_secs, "program pause ended");
+            }
+            Ok(Ok(None)) => {}
+            Ok(Err(err)) => warn!(error = %err, "failed to record program status"),
+            Err(err) => warn!(error = %err, "program status record failed"),
+        }
+    }
+}
@@ -0,0 +1,265 @@
+use anyhow::{Context, Result, bail};
+use keeper_lib::types::enums::MarketType;
+use serde::Deserialize;
+use std::{fs, sync::Arc};
+
+use crate::catalog::AssetCatalog;
+
+/// Recurring round templates for the create-round keeper
+#[derive(Clone, Debug, Deserialize)]
+pub struct Schedule {
+    #[serde(rename = "template", default)]
+    pub templates: Vec<RoundTemplate>,
+}
+
+/// One recurring round, e.g. "single-asset gold, every hour, 55-minute duration"
+#[derive(Clone, Debug, Deserialize)]
+pub struct RoundTemplate {
+    pub name: String,
+    pub market_type: TemplateMarketType,
+    /// A round starts on every multiple of this (unix time), plus `offset_secs`
+    pub every_secs: u64,
+    #[serde(default)]
+    pub offset_secs: u64,
+    pub duration_secs: u64,
+    /// Rounds starting up to this far ahead are created
+    pub lead_secs: u64,
+    /// Asset catalog file of a group battle template's rounds
+    #[serde(default)]
+    pub catalog: Option<String>,
+    /// The loaded `catalog`
+    #[serde(skip)]
+    pub assets: Option<Arc<AssetCatalog>>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum TemplateMarketType {
+    SingleAsset,
+    GroupBattle,
+}
+
+impl From<TemplateMarketType> for MarketType {
+    fn from(market_type: TemplateMarketType) -> Self {
+        match market_type {
+            TemplateMarketType::SingleAsset => MarketType::SingleAsset,
+            TemplateMarketType::GroupBattle => MarketType::GroupBattle,
+        }
+    }
+}
+
+impl Schedule {
+    pub fn load(path: &str) -> Result<Self> {
+        let raw = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read schedule file: {}", path))?;
+        let mut schedule: Schedule =
+            toml::from_str(&raw).with_context(|| format!("Invalid schedule file: {}", path))?;
+
+        for template in &mut schedule.templates {
+            template
+                .validate()
+                .with_context(|| format!("Invalid template {}", template.name))?;
+            if let Some(catalog) = &template.catalog {
+                template.assets = Some(Arc::new(AssetCatalog::load(catalog)?));
+            }
+        }
+
+        Ok(schedule)
+    }
+}
+
+impl RoundTemplate {
+    fn validate(&self) -> Result<()> {
+        if self.every_secs == 0 {
+            bail!("every_secs must be greater than 0");
+        }
+        if self.offset_secs >= self.every_secs {
+            bail!("offset_secs must be less than every_secs");
+        }
+        if self.duration_secs == 0 {
+            bail!("duration_secs must be greater than 0");
+        }
+        match (self.market_type, &self.catalog) {
+            (TemplateMarketType::GroupBattle, None) => {
+                bail!("group_battle templates need an asset catalog")
+            }
+            (TemplateMarketType::SingleAsset, Some(_)) => {
+                bail!("catalog is only used by group_battle templates")
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Check the duration leaves room for betting before the cutoff window, and that no
+    /// other of the `templates` starts rounds of the same market type at the same time
+    pub fn validate_against(
+        &self,
+        bet_cutoff_window_secs: i64,
+        templates: &[RoundTemplate],
+    ) -> Result<()> {
+        if self.duration_secs as i64 <= bet_cutoff_window_secs {
+            bail!(
+                "duration_secs {} must exceed the bet cutoff window ({}s)",
+                self.duration_secs,
+                bet_cutoff_window_secs
+            );
+        }
+        if let Some(other) = templates
+            .iter()
+            .find(|other| !std::ptr::eq(*other, self) && self.overlaps(other))
+        {
+            bail!("shares round start times with template {}", other.name);
+        }
+        Ok(())
+    }
+
+    /// Whether both templates have a slot of the same market type starting at the same time
+    fn overlaps(&self, other: &RoundTemplate) -> bool {
+        let step = gcd(self.every_secs, other.every_secs);
+        self.market_type == other.market_type
+            && self
+                .offset_secs
+                .abs_diff(other.offset_secs)
+                .is_multiple_of(step)
+    }
+
+    /// Whether a round with these times is one of the template's slots
+    pub fn matches(&self, start_time: i64, end_time: i64) -> bool {
+        let every = self.every_secs as i64;
+        (start_time - self.offset_secs as i64).rem_euclid(every) == 0
+            && end_time - start_time == self.duration_secs as i64
+    }
+
+    /// `(start_time, end_time)` of the template's rounds starting in `(after, until]`
+    pub fn slots(&self, after: i64, until: i64) -> Vec<(i64, i64)> {
+        let every = self.every_secs as i64;
+        let offset = self.offset_secs as i64;
+
+        // First slot start strictly after `after`
+        let mut start = (after - offset).div_euclid(every) * every + offset;
+        if start <= after {
+            start += every;
+        }
+
+        let mut out = Vec::new();
+        while start <= until {
+            out.push((start, start + self.duration_secs as i64));
+            start += every;
+        }
+        out
+    }
+}
+
+fn gcd(a: u64, b: u64) -> u64 {
+    if b == 0 { a } else { gcd(b, a % b) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn template(every_secs: u64, offset_secs: u64, duration_secs: u64) -> RoundTemplate {
+        RoundTemplate {
+            name: "test".to_string(),
+            market_type: TemplateMarketType::SingleAsset,
+            every_secs,
+            offset_secs,
+            duration_secs,
+            lead_secs: 0,
+            catalog: None,
+            assets: None,
+        }
+    }
+
+    #[test]
+    fn slots_exclude_after_and_include_until() {
+        let t = template(3600, 0, 3300);
+
+        assert_eq!(
+            t.slots(3600, 10_800),
+            vec![(7200, 10_500), (10_800, 14_100)]
+        );
+        assert_eq!(t.slots(3599, 3600), vec![(3600, 6900)]);
+        assert!(t.slots(3600, 7199).is_empty());
+    }
+
+    #[test]
+    fn slots_apply_offset() {
+        let t = template(3600, 1800, 600);
+
+        assert_eq!(t.slots(0, 7200), vec![(1800, 2400), (5400, 6000)]);
+        assert_eq!(t.slots(1800, 5400), vec![(5400, 6000)]);
+        assert_eq!(t.slots(1799, 1800), vec![(1800, 2400)]);
+    }
+
+    #[test]
+    fn slots_before_the_epoch() {
+        let t = template(3600, 1800, 600);
+
+        assert_eq!(t.slots(-1, 1800), vec![(1800, 2400)]);
+        assert_eq!(t.slots(-5000, -1), vec![(-1800, -1200)]);
+        assert_eq!(t.slots(-5401, -5400), vec![(-5400, -4800)]);
+    }
+
+    #[test]
+    fn slots_empty_when_until_not_after() {
+        let t = template(60, 0, 30);
+
+        assert!(t.slots(120, 120).is_empty());
+        assert!(t.slots(120, 60).is_empty());
+    }
+
+    #[test]
+    fn matches_its_own_slots() {
+        let t = template(21_600, 1800, 18_000);
+
+        for (start, end) in t.slots(-100_000, 100_000) {
+            assert!(t.matches(start, end));
+        }
+    }
+
+    #[test]
+    fn matches_checks_offset_and_duration() {
+        let t = template(3600, 1800, 600);
+
+        assert!(t.matches(1800, 2400));
+        assert!(t.matches(-1800, -1200));
+        assert!(!t.matches(3600, 4200));
+        assert!(!t.matches(1800, 2401));
+    }
+
+    #[test]
+    fn validate_rejects_offset_past_period() {
+        assert!(template(3600, 0, 600).validate().is_ok());
+        assert!(template(3600, 3600, 600).validate().is_err());
+        assert!(template(0, 0, 600).validate().is_err());
+        assert!(template(3600, 0, 0).validate().is_err());
+    }
+
+    #[test]
+    fn overlaps_when_start_times_coincide() {
+        assert!(template(3600, 0, 600).overlaps(&template(3600, 0, 1200)));
+        assert!(template(3600, 1800, 600).overlaps(&template(5400, 0, 600)));
+        assert!(!template(3600, 0, 600).overlaps(&template(3600, 1800, 600)));
+        assert!(!template(7200, 0, 600).overlaps(&template(7200, 3600, 600)));
+
+        let mut group = template(3600, 0, 600);
+        group.market_type = TemplateMarketType::GroupBattle;
+        assert!(!template(3600, 0, 600).overlaps(&group));
+    }
+
+    #[test]
+    fn validate_against_rejects_overlapping_templates() {
+        let templates = vec![template(3600, 0, 600), template(3600, 1800, 600)];
+        for t in &templates {
+            assert!(t.validate_against(300, &templates).is_ok());
+        }
+
+        let templates = vec![template(3600, 0, 600), template(1800, 0, 900)];
+        for t in &templates {
+            assert!(t.validate_against(300, &templates).is_err());
+        }
+        assert!(templates[0].validate_against(600, &templates[..1]).is_err());
+    }
+}
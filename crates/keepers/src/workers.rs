@@ -0,0 +1,73 @@
+use anyhow::{Result, anyhow};
+use std::{
+    collections::HashSet,
+    sync::{Arc, Mutex},
+};
+use tokio::{sync::Semaphore, task::JoinHandle};
+
+/// Bounded pool running round jobs on the blocking threads, at most one job per round
+///
+/// Jobs send on the blocking RPC client, only discovery uses the nonblocking clients.
+pub struct RoundWorkers {
+    permits: Arc<Semaphore>,
+    in_progress: Arc<Mutex<HashSet<u64>>>,
+}
+
+impl RoundWorkers {
+    pub fn new(concurrency: usize) -> Self {
+        Self {
+            permits: Arc::new(Semaphore::new(concurrency.max(1))),
+            in_progress: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    /// Queue a job for the round, `None` if the round is already queued or running
+    pub fn spawn<T, F>(&self, round_id: u64, job: F) -> Option<JoinHandle<Result<T>>>
+    where
+        T: Send + 'static,
+        F: FnOnce() -> Result<T> + Send + 'static,
+    {
+        let lock = RoundLock::acquire(&self.in_progress, round_id)?;
+        let permits = Arc::clone(&self.permits);
+
+        Some(tokio::spawn(async move {
+            let _lock = lock;
+            let _permit = permits
+                .acquire_owned()
+                .await
+                .map_err(|_| anyhow!("round workers closed"))?;
+            tokio::task::spawn_blocking(job)
+                .await
+                .map_err(|e| anyhow!("round {} job panicked: {}", round_id, e))?
+        }))
+    }
+}
+
+/// Wait for a round job, flattening task failures into the job's error
+pub async fn join<T>(job: JoinHandle<Result<T>>) -> Result<T> {
+    job.await.map_err(|e| anyhow!("round job failed: {}", e))?
+}
+
+/// Marks a round as in progress until dropped
+struct RoundLock {
+    round_id: u64,
+    in_progress: Arc<Mutex<HashSet<u64>>>,
+}
+
+impl RoundLock {
+    fn acquire(in_progress: &Arc<Mutex<HashSet<u64>>>, round_id: u64) -> Option<Self> {
+        if !in_progress.lock().unwrap().insert(round_id) {
+            return None;
+        }
+        Some(Self {
+            round_id,
+            in_progress: Arc::clone(in_progress),
+        })
+    }
+}
+
+impl Drop for RoundLock {
+    fn drop(&mut self) {
+        self.in_progress.lock().unwrap().remove(&self.round_id);
+    }
+}